@@ -0,0 +1,186 @@
+//! Headless command-line mode for installing and launching versions without opening a window.
+
+use std::io::Write;
+
+use iced::futures::StreamExt;
+
+use crate::download::{self, DownloadEvent, DownloadOptions, DownloadPhase, DownloadUpdate};
+use crate::utils::{bytes_to_human_readable, format_eta};
+use crate::version::{Version, VersionRange};
+use crate::{Launcher, View};
+
+const USAGE: &str = "\
+Usage: mineplace3d-launcher [COMMAND]
+
+Commands:
+  list                List the installed versions
+  download <VERSION>  Download a version, or the newest one matching `latest`, a wildcard
+                      like `0.3.*` or a range like `>=0.3.0,<0.4.0`
+  run <VERSION>       Run an installed version, or `latest` for the newest one
+
+Without a command the launcher window is opened.";
+
+/// Connects stdout and stderr to the console the launcher was started from. Release builds on
+/// Windows are GUI programs without a console of their own, so output would otherwise be lost.
+#[cfg(windows)]
+pub fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{ATTACH_PARENT_PROCESS, AttachConsole};
+
+    // SAFETY: AttachConsole takes no pointers; it fails harmlessly when there is no parent
+    // console or one is already attached
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Runs the subcommand in `args` and returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start the async runtime: {}", e);
+            return 1;
+        }
+    };
+
+    // Startup problems are already reported on stderr by `Launcher::new`
+    let (mut launcher, _) = Launcher::new();
+    // There's no one to ask where a new install should go, so it goes in the default place
+    if launcher.view == View::FirstRun {
+        let game_dir = launcher.launcher_settings.game_dir.clone();
+        if let Err(e) = launcher.finish_first_run(game_dir) {
+            eprintln!("{}", e);
+            return 1;
+        }
+    }
+
+    let result = match args {
+        [command] if command == "list" => {
+            list(&launcher);
+            Ok(())
+        }
+        [command, version] if command == "download" => {
+            runtime.block_on(download(&mut launcher, version))
+        }
+        [command, version] if command == "run" => run_installed(&mut launcher, version),
+        [flag] if flag == "-h" || flag == "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+fn list(launcher: &Launcher) {
+    let mut versions: Vec<&Version> = launcher.versions.iter().collect();
+    versions.sort_by(|a, b| b.cmp(a));
+    if versions.is_empty() {
+        println!("No versions installed");
+    }
+    for version in versions {
+        println!("v{}", version);
+    }
+}
+
+async fn download(launcher: &mut Launcher, version: &str) -> Result<(), String> {
+    if launcher.launcher_settings.offline {
+        return Err("Offline mode is on; turn it off in the settings to download".to_string());
+    }
+    let client = download::build_client(&launcher.launcher_settings)?;
+    let manifest = download::fetch_manifest(
+        client.clone(),
+        launcher.launcher_settings.releases_repo.clone(),
+    )
+    .await?;
+    let range = version.parse::<VersionRange>()?;
+    let version = manifest.resolve_range(&range).ok_or_else(|| match range {
+        VersionRange::Latest => "The manifest does not list a latest version".to_string(),
+        _ => format!("No available version matches \"{}\"", version),
+    })?;
+    if launcher.versions.contains(&version) {
+        println!("Version v{} is already installed", version);
+        return Ok(());
+    }
+
+    println!("Downloading v{}", version);
+    let mut events = std::pin::pin!(download::fetch(
+        client,
+        manifest.clone(),
+        launcher.launcher_settings.game_dir.clone(),
+        version,
+        DownloadOptions::from(&launcher.launcher_settings),
+    ));
+    let mut result = Err("The download ended without a result".to_string());
+    while let Some(event) = events.next().await {
+        match event {
+            DownloadEvent::Update(update) => print_update(&update),
+            DownloadEvent::Done(done) => result = done,
+        }
+    }
+
+    let version = result?;
+    launcher.manifest = Some(manifest);
+    launcher.record_install(version, launcher.verified_manifest_checksum(version));
+    println!("Installed v{}", version);
+    Ok(())
+}
+
+fn print_update(update: &DownloadUpdate) {
+    match update {
+        DownloadUpdate::Progress {
+            phase: DownloadPhase::Verifying,
+            ..
+        } => println!("\n{}", DownloadPhase::Verifying),
+        DownloadUpdate::Progress {
+            phase,
+            progress,
+            speed,
+            eta_secs,
+        } => {
+            print!(
+                "\r{} {:.1}% • {}/s • ETA {}   ",
+                phase,
+                progress * 100.0,
+                bytes_to_human_readable(*speed),
+                format_eta(*eta_secs)
+            );
+            let _ = std::io::stdout().flush();
+        }
+        DownloadUpdate::Retrying { attempt, retries } => {
+            println!("\nRetrying ({}/{})...", attempt, retries);
+        }
+        DownloadUpdate::Finished => println!(),
+        DownloadUpdate::Failed { .. } | DownloadUpdate::None => {}
+    }
+}
+
+fn run_installed(launcher: &mut Launcher, version: &str) -> Result<(), String> {
+    let version = if version == "latest" {
+        launcher
+            .get_latest_downloaded()
+            .ok_or_else(|| "You have no versions downloaded".to_string())?
+    } else {
+        version.parse::<Version>()?
+    };
+
+    let mut child = launcher.run_version(version, &launcher.launcher_settings.game_dir)?;
+    let started_at = std::time::Instant::now();
+    launcher.record_launch(version);
+    println!("Running v{}", version);
+    let status = child.wait();
+    launcher.record_play_time(version, started_at.elapsed());
+    let status = status.map_err(|e| format!("Failed to wait for v{}: {}", version, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("v{} exited with {}", version, status))
+    }
+}
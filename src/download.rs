@@ -0,0 +1,1415 @@
+//! Fetching the manifest and installing versions over the network.
+
+use std::path::{Path, PathBuf};
+
+use iced::futures::channel::mpsc::Sender;
+use iced::futures::{Stream, StreamExt, future, stream};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::LauncherSettings;
+use crate::utils::{self, Manifest, version_file_name};
+use crate::version::Version;
+
+/// How many times a failed network request is retried before giving up
+const NETWORK_RETRIES: u32 = 3;
+
+/// Size of the reads used when copying a version from a local mirror
+const LOCAL_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Name SDL2 is installed under next to the game binaries
+pub const SDL2_FILE_NAME: &str = if cfg!(target_os = "windows") {
+    "SDL2.dll"
+} else {
+    "libSDL2-2.0.so.0"
+};
+
+/// The step of an install a [`DownloadUpdate::Progress`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadPhase {
+    Game,
+    Sdl2,
+    Verifying,
+}
+
+impl std::fmt::Display for DownloadPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DownloadPhase::Game => write!(f, "Downloading game..."),
+            DownloadPhase::Sdl2 => write!(f, "Downloading SDL2..."),
+            DownloadPhase::Verifying => write!(f, "Verifying checksum..."),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub enum DownloadUpdate {
+    Progress {
+        phase: DownloadPhase,
+        progress: f32,
+        /// Bytes per second, averaged over the last few samples
+        speed: f32,
+        /// Estimated seconds until the download finishes
+        eta_secs: f32,
+    },
+    Retrying {
+        attempt: u32,
+        retries: u32,
+    },
+    Finished,
+    Failed {
+        last_progress: Option<f32>,
+    },
+    #[default]
+    None,
+}
+
+/// An item of a download's progress stream.
+#[derive(Debug, Clone)]
+pub enum DownloadEvent<T> {
+    Update(DownloadUpdate),
+    /// The outcome of the download, always the last item
+    Done(Result<T, String>),
+}
+
+impl DownloadUpdate {
+    pub fn new(phase: DownloadPhase, progress: f32, speed: f32, eta_secs: f32) -> Self {
+        Self::Progress {
+            phase,
+            progress,
+            speed,
+            eta_secs,
+        }
+    }
+}
+
+/// The settings a download needs, captured when it starts.
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    verify_checksums: bool,
+    github_token: Option<String>,
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    auto_install_sdl2: bool,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    bundle_sdl2: bool,
+    /// How long the download may go without receiving data
+    timeout: std::time::Duration,
+    /// Cap on the download speed in bytes per second
+    max_bytes_per_sec: Option<u64>,
+    /// How many ranges of a large file are downloaded at once
+    segments: u64,
+    /// Where binaries are downloaded before being moved into the versions directory
+    staging_dir: Option<PathBuf>,
+}
+
+impl From<&LauncherSettings> for DownloadOptions {
+    fn from(settings: &LauncherSettings) -> Self {
+        Self {
+            verify_checksums: settings.verify_checksums,
+            github_token: settings.github_token.clone(),
+            auto_install_sdl2: settings.auto_install_sdl2,
+            bundle_sdl2: settings.bundle_sdl2,
+            timeout: settings.network_timeout(),
+            max_bytes_per_sec: settings.max_download_bytes_per_sec,
+            segments: u64::from(settings.download_segments.max(1)),
+            staging_dir: settings.download_staging_dir.clone(),
+        }
+    }
+}
+
+/// Builds the HTTP client every launcher request goes through.
+pub fn build_client(settings: &LauncherSettings) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(settings.network_timeout())
+        .read_timeout(settings.network_timeout());
+    if let Some(proxy) = &settings.http_proxy {
+        builder = builder
+            .proxy(reqwest::Proxy::all(proxy).map_err(|e| format!("Invalid HTTP proxy: {}", e))?);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Fetches the manifest of `releases_repo`, or reads it from a local mirror directory.
+pub async fn fetch_manifest(
+    client: reqwest::Client,
+    releases_repo: String,
+) -> Result<Manifest, String> {
+    if let Some(mirror) = utils::local_mirror(&releases_repo) {
+        let path = mirror.join("manifest.json");
+        let data = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut manifest: Manifest = serde_json::from_slice(&data).map_err(|e| e.to_string())?;
+        // Mirrors keep each release's assets in a folder named after its tag next to the
+        // manifest, wherever the copied manifest says they were hosted
+        manifest.base = reqwest::Url::from_directory_path(&mirror)
+            .map_err(|_| format!("Invalid mirror directory {}", mirror.display()))?
+            .to_string();
+        return Ok(manifest);
+    }
+
+    let request = client
+        .get(utils::manifest_url(&releases_repo))
+        .header("User-Agent", "mineplace3d-launcher");
+    get_with_retry(request, NETWORK_RETRIES, None)
+        .await?
+        .json()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Fetches the release notes of the GitHub release tagged `tag` in `releases_repo`, which are
+/// empty if the release has none.
+pub async fn fetch_release_notes(
+    client: reqwest::Client,
+    releases_repo: String,
+    tag: String,
+    github_token: Option<String>,
+) -> Result<String, String> {
+    #[derive(serde::Deserialize)]
+    struct Release {
+        body: Option<String>,
+    }
+
+    if utils::local_mirror(&releases_repo).is_some() {
+        return Err("Release notes aren't available from a local mirror".to_string());
+    }
+
+    let url = format!(
+        "https://api.github.com/repos/{}/releases/tags/{}",
+        releases_repo, tag
+    );
+    let request = with_github_auth(
+        client
+            .get(&url)
+            .header("User-Agent", "mineplace3d-launcher")
+            .header("Accept", "application/vnd.github+json"),
+        &url,
+        github_token.as_deref(),
+    );
+    let response = get_with_retry(request, NETWORK_RETRIES, None).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server responded with {}", response.status()));
+    }
+    let release: Release = response.json().await.map_err(|e| e.to_string())?;
+    Ok(release.body.unwrap_or_default())
+}
+
+/// The newest published release of a GitHub repository.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LatestRelease {
+    pub tag_name: String,
+    /// The release's page on GitHub
+    pub html_url: String,
+}
+
+/// Fetches the newest non-prerelease GitHub release of `repo`.
+pub async fn fetch_latest_release(
+    client: reqwest::Client,
+    repo: String,
+    github_token: Option<String>,
+) -> Result<LatestRelease, String> {
+    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
+    let request = with_github_auth(
+        client
+            .get(&url)
+            .header("User-Agent", "mineplace3d-launcher")
+            .header("Accept", "application/vnd.github+json"),
+        &url,
+        github_token.as_deref(),
+    );
+    let response = get_with_retry(request, NETWORK_RETRIES, None).await?;
+    if !response.status().is_success() {
+        return Err(format!("Server responded with {}", response.status()));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Adds `token` as a bearer token when `url` points at GitHub, so it is never sent to
+/// mirrors or other hosts.
+fn with_github_auth(
+    request: reqwest::RequestBuilder,
+    url: &str,
+    token: Option<&str>,
+) -> reqwest::RequestBuilder {
+    let is_github = reqwest::Url::parse(url).is_ok_and(|url| {
+        url.host_str()
+            .is_some_and(|host| host == "github.com" || host.ends_with(".github.com"))
+    });
+    match token {
+        Some(token) if is_github => request.bearer_auth(token),
+        _ => request,
+    }
+}
+
+fn is_github_rate_limited(response: &reqwest::Response) -> bool {
+    matches!(
+        response.status(),
+        reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::TOO_MANY_REQUESTS
+    ) && response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .is_some_and(|remaining| remaining == "0")
+}
+
+/// Sends `request`, retrying transient failures (timeouts, connection errors and server
+/// errors) up to `retries` times with exponential backoff.
+///
+/// Other unsuccessful statuses such as 404 are returned right away for the caller to handle.
+async fn get_with_retry(
+    request: reqwest::RequestBuilder,
+    retries: u32,
+    mut progress_tx: Option<&mut Sender<DownloadUpdate>>,
+) -> Result<reqwest::Response, String> {
+    let mut attempt = 0;
+    loop {
+        let this_request = request
+            .try_clone()
+            .ok_or_else(|| "Request cannot be retried".to_string())?;
+        let error = match this_request.send().await {
+            Ok(response) if is_github_rate_limited(&response) => {
+                return Err("GitHub rate limit hit, add a token in Settings".to_string());
+            }
+            Ok(response) if !response.status().is_server_error() => return Ok(response),
+            Ok(response) => format!("Server responded with {}", response.status()),
+            Err(e) if e.is_timeout() => "Connection timed out".to_string(),
+            Err(e) if e.is_connect() || e.is_request() => e.to_string(),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        if attempt >= retries {
+            return Err(error);
+        }
+        attempt += 1;
+
+        if let Some(tx) = progress_tx.as_deref_mut() {
+            let _ = tx.try_send(DownloadUpdate::Retrying { attempt, retries });
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500 << (attempt - 1))).await;
+    }
+}
+
+/// How often download progress is reported
+const PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Downloads smaller than this aren't worth splitting into segments
+const MIN_SEGMENTED_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Recent (bytes, duration) windows used to smooth out the reported speed.
+#[derive(Debug, Default)]
+struct SpeedSamples(std::collections::VecDeque<(u64, std::time::Duration)>);
+
+impl SpeedSamples {
+    const LEN: usize = 8;
+
+    fn push(&mut self, bytes: u64, elapsed: std::time::Duration) {
+        if self.0.len() == Self::LEN {
+            self.0.pop_front();
+        }
+        self.0.push_back((bytes, elapsed));
+    }
+
+    /// Bytes per second over the recent windows.
+    fn speed(&self) -> f32 {
+        let bytes: u64 = self.0.iter().map(|(b, _)| b).sum();
+        let time: std::time::Duration = self.0.iter().map(|(_, t)| *t).sum();
+        bytes as f32 / time.as_secs_f32()
+    }
+}
+
+/// Describes an error from the middle of a response body.
+fn chunk_error(e: reqwest::Error) -> String {
+    if e.is_timeout() {
+        "Connection timed out".to_string()
+    } else {
+        e.to_string()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn download_to_file(
+    content_length: Option<u64>,
+    mut stream: impl iced::futures::Stream<Item = Result<bytes::Bytes, String>> + Unpin,
+    path: std::path::PathBuf,
+    resume_from: u64,
+    phase: DownloadPhase,
+    stall_timeout: std::time::Duration,
+    max_bytes_per_sec: Option<u64>,
+    progress_tx: &mut Sender<DownloadUpdate>,
+) -> Result<(), String> {
+    // When resuming, keep the bytes we already have and continue after them
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(resume_from == 0)
+        .open(&path)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let content_length = content_length.map(|len| len + resume_from);
+
+    // Allocate the whole file up front when its size is known to avoid fragmentation
+    if let Some(total) = content_length {
+        file.set_len(total).await.map_err(|e| e.to_string())?;
+    }
+    file.seek(std::io::SeekFrom::Start(resume_from))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut file = tokio::io::BufWriter::new(file);
+
+    let mut downloaded = resume_from;
+    let mut last_progress = 0.0;
+
+    let mut last_tick = std::time::Instant::now();
+    let mut downloaded_since_last = 0u64;
+    let mut samples = SpeedSamples::default();
+
+    let mut last_chunk_at = std::time::Instant::now();
+    let started_at = std::time::Instant::now();
+
+    let result = loop {
+        tokio::select! {
+            chunk = stream.next() => {
+                match chunk {
+                    Some(Ok(bytes)) => {
+                        last_chunk_at = std::time::Instant::now();
+
+                        if let Err(e) = file.write_all(&bytes).await {
+                            break Err(e.to_string());
+                        }
+
+                        let len = bytes.len() as u64;
+                        downloaded += len;
+                        downloaded_since_last += len;
+
+                        // Hold off until the average rate since the start is back under the cap
+                        if let Some(limit) = max_bytes_per_sec.filter(|limit| *limit > 0) {
+                            let allowed = std::time::Duration::from_secs_f64(
+                                (downloaded - resume_from) as f64 / limit as f64,
+                            );
+                            if let Some(wait) = allowed.checked_sub(started_at.elapsed()) {
+                                tokio::time::sleep(wait).await;
+                            }
+                            // Time spent waiting isn't a stalled connection
+                            last_chunk_at = std::time::Instant::now();
+                        }
+
+                        let elapsed = last_tick.elapsed();
+
+                        if elapsed >= PROGRESS_INTERVAL {
+                            samples.push(downloaded_since_last, elapsed);
+
+                            if let Some(total) = content_length {
+                                let progress = downloaded as f32 / total as f32;
+                                last_progress = progress;
+
+                                let speed = samples.speed();
+                                let eta_secs = if speed > 0.0 {
+                                    total.saturating_sub(downloaded) as f32 / speed
+                                } else {
+                                    0.0
+                                };
+
+                                let _ = progress_tx
+                                    .try_send(DownloadUpdate::new(phase, progress, speed, eta_secs));
+                            }
+
+                            downloaded_since_last = 0;
+                            last_tick = std::time::Instant::now();
+                        }
+                    }
+                    Some(Err(e)) => {
+                        let _ = progress_tx.try_send(DownloadUpdate::Failed {
+                            last_progress: Some(last_progress),
+                        });
+                        break Err(e);
+                    }
+                    None => break Ok(()),
+                }
+            }
+
+            _ = tokio::time::sleep(stall_timeout) => {
+                if last_chunk_at.elapsed() >= stall_timeout {
+                    let _ = progress_tx.try_send(DownloadUpdate::Failed {
+                        last_progress: Some(last_progress),
+                    });
+                    break Err("Connection timed out".to_string());
+                }
+            }
+        }
+    };
+
+    file.flush().await.map_err(|e| e.to_string())?;
+
+    // A connection closed early ends the stream just like a finished download does. What did
+    // arrive is kept so a retry resumes after it, unless there is more of it than expected.
+    if result.is_ok()
+        && let Some(total) = content_length
+        && downloaded != total
+    {
+        if downloaded < total {
+            file.get_mut()
+                .set_len(downloaded)
+                .await
+                .map_err(|e| e.to_string())?;
+        } else {
+            drop(file);
+            let _ = tokio::fs::remove_file(&path).await;
+        }
+        let _ = progress_tx.try_send(DownloadUpdate::Failed {
+            last_progress: Some(last_progress),
+        });
+        return Err(format!(
+            "Incomplete download: got {} of {} bytes",
+            downloaded, total
+        ));
+    }
+
+    // Drop the preallocated tail so the partial file's size says how much can be resumed
+    if result.is_err() {
+        file.get_mut()
+            .set_len(downloaded)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    result
+}
+
+/// Refuses to download `needed` more bytes into `path` when its filesystem doesn't have the
+/// room, rather than failing partway with a write error.
+fn check_disk_space(path: &Path, needed: u64) -> Result<(), String> {
+    let dir = path.parent().unwrap_or(path);
+    match utils::available_space(dir) {
+        Some(available) if available < needed => {
+            let units = utils::ByteUnits::platform_default();
+            Err(format!(
+                "Not enough disk space in {}: need {}, have {}",
+                dir.display(),
+                utils::bytes_to_human_readable_u64(needed, units),
+                utils::bytes_to_human_readable_u64(available, units)
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Asks for the first byte of the file `request` fetches, returning the file's size if the
+/// server answers with a partial response, meaning it supports range requests.
+async fn probe_range_support(request: reqwest::RequestBuilder) -> Option<u64> {
+    let response = get_with_retry(
+        request.header(reqwest::header::RANGE, "bytes=0-0"),
+        NETWORK_RETRIES,
+        None,
+    )
+    .await
+    .ok()?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return None;
+    }
+    // Looks like `bytes 0-0/123456`
+    let content_range = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?;
+    content_range.rsplit_once('/')?.1.parse().ok()
+}
+
+/// Downloads the `total` bytes `request` fetches into `path` as `segments` ranges at once,
+/// reporting their combined progress.
+///
+/// Unlike [`download_to_file`], a failure removes the file, since the preallocated parts of
+/// it say nothing about which ranges arrived.
+#[allow(clippy::too_many_arguments)]
+async fn download_segmented(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    total: u64,
+    path: &Path,
+    segments: u64,
+    stall_timeout: std::time::Duration,
+    max_bytes_per_sec: Option<u64>,
+    progress_tx: &mut Sender<DownloadUpdate>,
+) -> Result<(), String> {
+    check_disk_space(path, total)?;
+    let file = tokio::fs::File::create(path)
+        .await
+        .map_err(|e| e.to_string())?;
+    file.set_len(total).await.map_err(|e| e.to_string())?;
+    drop(file);
+
+    let downloaded = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let segment_len = total.div_ceil(segments);
+    // Each segment gets an even share of the speed limit
+    let segment_limit = max_bytes_per_sec.map(|limit| (limit / segments).max(1));
+    let downloads = (0..segments)
+        .map(|i| i * segment_len)
+        .filter(|start| *start < total)
+        .map(|start| {
+            let end = (start + segment_len).min(total) - 1;
+            download_segment(
+                request(),
+                path,
+                start..=end,
+                stall_timeout,
+                segment_limit,
+                downloaded.clone(),
+            )
+        });
+    let mut all_downloads = std::pin::pin!(future::try_join_all(downloads));
+
+    let mut ticker = tokio::time::interval(PROGRESS_INTERVAL);
+    let mut last_tick = std::time::Instant::now();
+    let mut last_downloaded = 0;
+    let mut samples = SpeedSamples::default();
+    let result = loop {
+        tokio::select! {
+            result = &mut all_downloads => break result.map(|_| ()),
+            _ = ticker.tick() => {
+                let now_downloaded = downloaded.load(std::sync::atomic::Ordering::Relaxed);
+                samples.push(now_downloaded - last_downloaded, last_tick.elapsed());
+                last_downloaded = now_downloaded;
+                last_tick = std::time::Instant::now();
+
+                let speed = samples.speed();
+                let eta_secs = if speed > 0.0 {
+                    total.saturating_sub(now_downloaded) as f32 / speed
+                } else {
+                    0.0
+                };
+                let _ = progress_tx.try_send(DownloadUpdate::new(
+                    DownloadPhase::Game,
+                    now_downloaded as f32 / total as f32,
+                    speed,
+                    eta_secs,
+                ));
+            }
+        }
+    };
+
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(path).await;
+        let _ = progress_tx.try_send(DownloadUpdate::Failed {
+            last_progress: Some(
+                downloaded.load(std::sync::atomic::Ordering::Relaxed) as f32 / total as f32,
+            ),
+        });
+    }
+    result
+}
+
+/// Downloads the bytes in `range` of the file `request` fetches into the same place in `path`,
+/// adding every byte written to `downloaded`.
+async fn download_segment(
+    request: reqwest::RequestBuilder,
+    path: &Path,
+    range: std::ops::RangeInclusive<u64>,
+    stall_timeout: std::time::Duration,
+    max_bytes_per_sec: Option<u64>,
+    downloaded: std::sync::Arc<std::sync::atomic::AtomicU64>,
+) -> Result<(), String> {
+    let (start, end) = (*range.start(), *range.end());
+    let request = request.header(reqwest::header::RANGE, format!("bytes={}-{}", start, end));
+    let response = get_with_retry(request, NETWORK_RETRIES, None).await?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return Err(format!(
+            "Server answered the request for bytes {}-{} with {}",
+            start,
+            end,
+            response.status()
+        ));
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await
+        .map_err(|e| e.to_string())?;
+    file.seek(std::io::SeekFrom::Start(start))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut file = tokio::io::BufWriter::new(file);
+
+    let mut stream = response.bytes_stream();
+    let mut written = 0u64;
+    let started_at = std::time::Instant::now();
+    while let Some(chunk) = tokio::time::timeout(stall_timeout, stream.next())
+        .await
+        .map_err(|_| "Connection timed out".to_string())?
+    {
+        let bytes = chunk.map_err(chunk_error)?;
+        // A misbehaving server must not write past the end of the segment
+        let len = (bytes.len() as u64).min(end + 1 - start - written);
+        file.write_all(&bytes[..len as usize])
+            .await
+            .map_err(|e| e.to_string())?;
+        written += len;
+        downloaded.fetch_add(len, std::sync::atomic::Ordering::Relaxed);
+
+        if let Some(limit) = max_bytes_per_sec {
+            let allowed = std::time::Duration::from_secs_f64(written as f64 / limit as f64);
+            if let Some(wait) = allowed.checked_sub(started_at.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+    file.flush().await.map_err(|e| e.to_string())?;
+
+    let expected = end + 1 - start;
+    if written != expected {
+        return Err(format!(
+            "Incomplete download: got {} of {} bytes for bytes {}-{}",
+            written, expected, start, end
+        ));
+    }
+    Ok(())
+}
+
+/// Runs `job`, streaming the updates it sends and finishing with its result.
+///
+/// The result only comes once every update before it has been delivered.
+fn progress_stream<T, Fut>(
+    job: impl FnOnce(Sender<DownloadUpdate>) -> Fut,
+) -> impl Stream<Item = DownloadEvent<T>>
+where
+    Fut: Future<Output = Result<T, String>>,
+{
+    let (progress_tx, progress_rx) = iced::futures::channel::mpsc::channel(100);
+    let (result_tx, result_rx) = iced::futures::channel::oneshot::channel();
+    let job = job(progress_tx);
+
+    // The job owns the sender, so the updates end once it has finished
+    let runner = stream::once(async move {
+        let _ = result_tx.send(job.await);
+    })
+    .filter_map(|()| future::ready(None));
+    stream::select(progress_rx.map(DownloadEvent::Update), runner).chain(
+        stream::once(result_rx)
+            .filter_map(|result| future::ready(result.ok().map(DownloadEvent::Done))),
+    )
+}
+
+/// Where `version` is downloaded to before it is installed: a `.part` file in `staging_dir`
+/// when one is set, or in the versions directory of `game_dir`. The file only gets the version's
+/// real name once it has been checked, so an unfinished download is never taken for an install.
+pub fn download_path(game_dir: &Path, staging_dir: Option<&Path>, version: Version) -> PathBuf {
+    staging_dir
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| game_dir.join("versions"))
+        .join(format!(
+            "{}.{}",
+            version_file_name(version),
+            utils::PARTIAL_DOWNLOAD_EXTENSION
+        ))
+}
+
+/// Downloads `version` from the manifest into the versions directory of `game_dir`.
+pub fn fetch(
+    client: reqwest::Client,
+    manifest: Manifest,
+    game_dir: PathBuf,
+    version: Version,
+    options: DownloadOptions,
+) -> impl Stream<Item = DownloadEvent<Version>> {
+    progress_stream(move |progress_tx| {
+        fetch_version(client, manifest, game_dir, version, options, progress_tx)
+    })
+}
+
+async fn fetch_version(
+    client: reqwest::Client,
+    manifest: Manifest,
+    game_dir: PathBuf,
+    version: Version,
+    options: DownloadOptions,
+    mut progress_tx: Sender<DownloadUpdate>,
+) -> Result<Version, String> {
+    let entry = manifest
+        .versions
+        .get(&version)
+        .ok_or_else(|| format!("Version v{} not found in manifest", version))?;
+
+    let download_url = manifest.asset_url(version)?;
+
+    let exec_path = game_dir.join("versions").join(version_file_name(version));
+    let download_path = download_path(&game_dir, options.staging_dir.as_deref(), version);
+
+    // A file left behind by an interrupted download can be resumed instead of restarted
+    let existing_len = tokio::fs::metadata(&download_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let build_request = || {
+        with_github_auth(
+            client
+                .get(&download_url)
+                .header("User-Agent", "mineplace3d-launcher"),
+            &download_url,
+            options.github_token.as_deref(),
+        )
+    };
+
+    // Versions from a local mirror are copied instead of downloaded
+    let local_source = utils::local_file_path(&download_url);
+
+    // Fresh downloads of large files are split into segments when the server allows it
+    let segmented_size = if local_source.is_none() && options.segments > 1 && existing_len == 0 {
+        probe_range_support(build_request())
+            .await
+            .filter(|total| *total >= MIN_SEGMENTED_SIZE)
+    } else {
+        None
+    };
+
+    let expected_len = if let Some(source) = local_source {
+        copy_local(&source, &download_path, &options, &mut progress_tx)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to copy version v{} from {}: {}",
+                    version,
+                    source.display(),
+                    e
+                )
+            })?
+    } else if let Some(total) = segmented_size {
+        download_segmented(
+            build_request,
+            total,
+            &download_path,
+            options.segments,
+            options.timeout,
+            options.max_bytes_per_sec,
+            &mut progress_tx,
+        )
+        .await
+        .map_err(|e| format!("Failed to download version v{}: {}", version, e))?;
+        Some(total)
+    } else {
+        download_single(
+            build_request,
+            &download_path,
+            existing_len,
+            version,
+            &options,
+            &mut progress_tx,
+        )
+        .await?
+    };
+
+    // Segmented downloads preallocate the whole file, so its size alone proves little, but a
+    // wrong size always means the download is broken
+    let actual_len = tokio::fs::metadata(&download_path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", download_path.display(), e))?
+        .len();
+    if let Some(expected) = expected_len
+        && actual_len != expected
+    {
+        // A short file is only unfinished and can still be resumed
+        if actual_len > expected {
+            let _ = tokio::fs::remove_file(&download_path).await;
+        }
+        return Err(format!(
+            "Incomplete download of version v{}: got {} of {} bytes",
+            version, actual_len, expected
+        ));
+    }
+
+    if options.verify_checksums
+        && let Some(expected) = entry.checksum()
+    {
+        let _ = progress_tx.try_send(DownloadUpdate::new(DownloadPhase::Verifying, 1.0, 0.0, 0.0));
+        // Hashing reads the whole binary, so keep it off the async runtime
+        let hash_path = download_path.clone();
+        let actual = tokio::task::spawn_blocking(move || utils::sha256_file(&hash_path))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result.map_err(|e| e.to_string()))
+            .map_err(|e| {
+                format!(
+                    "Failed to compute checksum of {}: {}",
+                    download_path.display(),
+                    e
+                )
+            })?;
+        if actual != expected {
+            let _ = tokio::fs::remove_file(&download_path).await;
+            return Err(format!(
+                "Checksum mismatch for version v{}: expected {}, got {}",
+                version, expected, actual
+            ));
+        }
+    }
+
+    // SDL2 goes in before the binary, so a failure here leaves no unrecorded version behind
+    // Are we on windows? If so, install SDL2.dll if not present
+    #[cfg(target_os = "windows")]
+    if options.auto_install_sdl2 {
+        ensure_sdl2(&client, &game_dir, options.timeout, &mut progress_tx).await?;
+    }
+    #[cfg(target_os = "linux")]
+    if options.bundle_sdl2 {
+        ensure_sdl2(&client, &game_dir, options.timeout, &mut progress_tx).await?;
+    }
+
+    utils::set_executable(&download_path).map_err(|e| {
+        format!(
+            "Failed to set permissions for {}: {}",
+            download_path.display(),
+            e
+        )
+    })?;
+
+    // Only a complete, verified download is moved into place
+    let (from, to) = (download_path.clone(), exec_path.clone());
+    tokio::task::spawn_blocking(move || utils::move_file(&from, &to))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result.map_err(|e| e.to_string()))
+        .map_err(|e| {
+            format!(
+                "Failed to move {} to {}: {}",
+                download_path.display(),
+                exec_path.display(),
+                e
+            )
+        })?;
+
+    let _ = progress_tx.try_send(DownloadUpdate::Finished);
+
+    Ok(version)
+}
+
+/// Downloads the file `request` fetches into `path` in one stream, resuming after the
+/// `existing_len` bytes a previous attempt left behind. Returns the size of the whole file
+/// when the server said what it is.
+async fn download_single(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    path: &Path,
+    existing_len: u64,
+    version: Version,
+    options: &DownloadOptions,
+    progress_tx: &mut Sender<DownloadUpdate>,
+) -> Result<Option<u64>, String> {
+    let mut first_request = request();
+    if existing_len > 0 {
+        first_request =
+            first_request.header(reqwest::header::RANGE, format!("bytes={}-", existing_len));
+    }
+
+    let mut download_response =
+        get_with_retry(first_request, NETWORK_RETRIES, Some(&mut *progress_tx))
+            .await
+            .map_err(|e| format!("Failed to download asset: {}", e))?;
+
+    // The existing file is already at least as large as the asset, so start over
+    if download_response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        download_response = get_with_retry(request(), NETWORK_RETRIES, Some(&mut *progress_tx))
+            .await
+            .map_err(|e| format!("Failed to download asset: {}", e))?;
+    }
+
+    if !download_response.status().is_success() {
+        return Err(format!("Failed to download version v{}", version));
+    }
+
+    // Servers without range support answer with 200 and the whole file
+    let resume_from = if download_response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+        existing_len
+    } else {
+        0
+    };
+
+    let total_size = download_response.content_length();
+    if let Some(remaining) = total_size {
+        check_disk_space(path, remaining)?;
+    }
+    let stream = download_response
+        .bytes_stream()
+        .map(|chunk| chunk.map_err(chunk_error));
+
+    download_to_file(
+        total_size,
+        stream,
+        path.to_path_buf(),
+        resume_from,
+        DownloadPhase::Game,
+        options.timeout,
+        options.max_bytes_per_sec,
+        progress_tx,
+    )
+    .await?;
+    Ok(total_size.map(|remaining| remaining + resume_from))
+}
+
+/// Copies `source` from a local mirror into `path` in chunks, reporting progress the same way a
+/// download does. Returns the size of the copied file.
+async fn copy_local(
+    source: &Path,
+    path: &Path,
+    options: &DownloadOptions,
+    progress_tx: &mut Sender<DownloadUpdate>,
+) -> Result<Option<u64>, String> {
+    let file = tokio::fs::File::open(source)
+        .await
+        .map_err(|e| e.to_string())?;
+    let total_size = file.metadata().await.map_err(|e| e.to_string())?.len();
+    check_disk_space(path, total_size)?;
+
+    let chunks = stream::unfold(file, |mut file| async move {
+        let mut buffer = vec![0; LOCAL_CHUNK_SIZE];
+        match file.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(len) => {
+                buffer.truncate(len);
+                Some((Ok(bytes::Bytes::from(buffer)), file))
+            }
+            Err(e) => Some((Err(e.to_string()), file)),
+        }
+    });
+
+    // Copying is cheap to repeat, so a partial file is always started over
+    download_to_file(
+        Some(total_size),
+        std::pin::pin!(chunks),
+        path.to_path_buf(),
+        0,
+        DownloadPhase::Game,
+        options.timeout,
+        None,
+        progress_tx,
+    )
+    .await?;
+    Ok(Some(total_size))
+}
+
+/// Checks an installed binary against the manifest's checksum, or against the size of the
+/// published asset when there is no checksum. Returns `Ok(false)` if the file doesn't match.
+pub async fn verify_version(
+    client: reqwest::Client,
+    manifest: Manifest,
+    game_dir: PathBuf,
+    version: Version,
+    github_token: Option<String>,
+) -> Result<bool, String> {
+    let exec_path = game_dir.join("versions").join(version_file_name(version));
+
+    let metadata = tokio::fs::metadata(&exec_path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", exec_path.display(), e))?;
+    if metadata.is_dir() {
+        return Err("App bundles can't be verified".to_string());
+    }
+
+    let expected_checksum = manifest
+        .versions
+        .get(&version)
+        .and_then(|entry| entry.checksum());
+    if let Some(expected) = expected_checksum {
+        let actual = tokio::task::spawn_blocking(move || utils::sha256_file(&exec_path))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to compute checksum: {}", e))?;
+        return Ok(actual == expected);
+    }
+
+    // Without a checksum, the asset size is the best we can do without downloading it
+    let asset_url = manifest.asset_url(version)?;
+    if let Some(source) = utils::local_file_path(&asset_url) {
+        let expected_size = tokio::fs::metadata(&source)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?
+            .len();
+        return Ok(metadata.len() == expected_size);
+    }
+    let request = with_github_auth(
+        client
+            .head(&asset_url)
+            .header("User-Agent", "mineplace3d-launcher"),
+        &asset_url,
+        github_token.as_deref(),
+    );
+    let response = get_with_retry(request, NETWORK_RETRIES, None)
+        .await
+        .map_err(|e| format!("Failed to fetch asset size: {}", e))?;
+    let expected_size = response
+        .content_length()
+        .ok_or_else(|| "The release lists neither a checksum nor a size".to_string())?;
+    Ok(metadata.len() == expected_size)
+}
+
+/// Where the SDL2 zip is downloaded to before the library is extracted from it.
+pub fn sdl2_temp_path() -> PathBuf {
+    std::env::temp_dir().join("mineplace3d-sdl2.zip")
+}
+
+/// Downloads SDL2 next to the game binaries unless it is already there.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+async fn ensure_sdl2(
+    client: &reqwest::Client,
+    game_dir: &Path,
+    timeout: std::time::Duration,
+    progress_tx: &mut Sender<DownloadUpdate>,
+) -> Result<(), String> {
+    if game_dir.join("versions").join(SDL2_FILE_NAME).exists() {
+        return Ok(());
+    }
+    install_sdl2(client, game_dir, timeout, progress_tx).await
+}
+
+/// Downloads SDL2 next to the game binaries even if it is already there.
+pub fn reinstall_sdl2(
+    client: reqwest::Client,
+    game_dir: PathBuf,
+    timeout: std::time::Duration,
+) -> impl Stream<Item = DownloadEvent<()>> {
+    progress_stream(move |mut progress_tx| async move {
+        install_sdl2(&client, &game_dir, timeout, &mut progress_tx).await
+    })
+}
+
+/// Downloads SDL2 next to the game binaries, replacing any existing copy once the new one
+/// has been fully extracted.
+async fn install_sdl2(
+    client: &reqwest::Client,
+    game_dir: &Path,
+    timeout: std::time::Duration,
+    progress_tx: &mut Sender<DownloadUpdate>,
+) -> Result<(), String> {
+    let _ = progress_tx.try_send(DownloadUpdate::new(DownloadPhase::Sdl2, 0.0, 0.0, 0.0));
+
+    let (sdl2_url, sdl2_sha256) = sdl2_download_url(client).await?;
+    let request = client
+        .get(sdl2_url)
+        .header("User-Agent", "mineplace3d-launcher");
+    let sdl2_response = get_with_retry(request, NETWORK_RETRIES, Some(&mut *progress_tx))
+        .await
+        .map_err(|e| format!("Failed to download SDL2: {}", e))?;
+
+    if !sdl2_response.status().is_success() {
+        return Err("Failed to download SDL2".to_string());
+    }
+
+    let temp_zip_path = sdl2_temp_path();
+    let sdl2_path = game_dir.join("versions").join(SDL2_FILE_NAME);
+
+    let total_size = sdl2_response.content_length();
+    let stream = sdl2_response
+        .bytes_stream()
+        .map(|chunk| chunk.map_err(chunk_error));
+
+    let result = async {
+        download_to_file(
+            total_size,
+            stream,
+            temp_zip_path.clone(),
+            0,
+            DownloadPhase::Sdl2,
+            timeout,
+            None,
+            progress_tx,
+        )
+        .await
+        .map_err(|e| format!("Failed to download SDL2: {}", e))?;
+
+        // The library ends up loaded into the game, so it has to be exactly the pinned build
+        if let Some(expected) = &sdl2_sha256 {
+            let hash_path = temp_zip_path.clone();
+            let actual = tokio::task::spawn_blocking(move || utils::sha256_file(&hash_path))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result.map_err(|e| e.to_string()))
+                .map_err(|e| format!("Failed to compute checksum of SDL2: {}", e))?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(format!(
+                    "Checksum mismatch for SDL2: expected {}, got {}",
+                    expected, actual
+                ));
+            }
+        }
+
+        let zip_path = temp_zip_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut zip = zip::ZipArchive::new(
+                std::fs::File::open(&zip_path)
+                    .map_err(|e| format!("Failed to open SDL2 zip file: {}", e))?,
+            )
+            .map_err(|e| format!("Failed to read SDL2 zip archive: {}", e))?;
+
+            let entry_name = zip
+                .file_names()
+                .find(|name| is_sdl2_entry(name))
+                .map(str::to_string)
+                .ok_or_else(|| "Failed to find SDL2 in zip archive".to_string())?;
+            let mut sdl2_file = zip
+                .by_name(&entry_name)
+                .map_err(|e| format!("Failed to find SDL2 in zip archive: {}", e))?;
+
+            // Extracted fully before touching the old copy, so a failure never leaves a broken
+            // library
+            let mut sdl2_data = Vec::new();
+            std::io::Read::read_to_end(&mut sdl2_file, &mut sdl2_data)
+                .map_err(|e| format!("Failed to extract SDL2: {}", e))?;
+            utils::write_atomic(&sdl2_path, sdl2_data)
+                .map_err(|e| format!("Failed to write {}: {}", SDL2_FILE_NAME, e))?;
+            utils::set_executable(&sdl2_path)
+                .map_err(|e| format!("Failed to set permissions for {}: {}", SDL2_FILE_NAME, e))
+        })
+        .await
+        .map_err(|e| format!("Failed to extract SDL2: {}", e))?
+    }
+    .await;
+
+    if let Err(e) = tokio::fs::remove_file(&temp_zip_path).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!(
+            "Failed to remove temporary SDL2 zip file {}: {}",
+            temp_zip_path.display(),
+            e
+        );
+    }
+    result?;
+
+    let _ = progress_tx.try_send(DownloadUpdate::Finished);
+
+    Ok(())
+}
+
+/// Whether the zip entry `name` is the SDL2 library itself rather than a header or one of the
+/// SDL2 add-on libraries shipped next to it.
+fn is_sdl2_entry(name: &str) -> bool {
+    let file_name = name.rsplit('/').next().unwrap_or(name);
+    #[cfg(target_os = "linux")]
+    return utils::is_shared_library(file_name, "libSDL2");
+    #[cfg(not(target_os = "linux"))]
+    return file_name == SDL2_FILE_NAME;
+}
+
+/// Where to download SDL2 from, with the SHA-256 digest the download must have when one is
+/// known.
+#[cfg(not(target_os = "linux"))]
+async fn sdl2_download_url(_client: &reqwest::Client) -> Result<(String, Option<String>), String> {
+    let url = if !cfg!(target_os = "windows") {
+        None
+    } else if cfg!(target_arch = "x86_64") {
+        Some("https://www.libsdl.org/release/SDL2-2.32.10-win32-x64.zip")
+    } else if cfg!(target_arch = "x86") {
+        Some("https://www.libsdl.org/release/SDL2-2.32.10-win32-x86.zip")
+    } else if cfg!(target_arch = "aarch64") {
+        // SDL doesn't provide official arm64 builds, so we use a community build
+        Some(
+            "https://www.github.com/mmozeiko/build-sdl2/releases/download/2025-12-28/SDL2-arm64-2025-12-28.zip",
+        )
+    } else {
+        None
+    };
+    url.map(|url| (url.to_string(), None))
+        .ok_or_else(|| "No SDL2.dll build is available for this platform".to_string())
+}
+
+/// The release of `pysdl2-dll` whose `libSDL2` is bundled on Linux. Files on PyPI can't be
+/// replaced once published, so pinning the release and checking its published digest pins the
+/// exact library.
+#[cfg(target_os = "linux")]
+const PYSDL2_DLL_VERSION: &str = "2.30.2";
+
+/// SDL doesn't publish Linux builds, so the manylinux wheel of the pinned `pysdl2-dll`
+/// release, which bundles a portable `libSDL2`, is looked up on PyPI instead, along with the
+/// SHA-256 digest PyPI lists for it.
+#[cfg(target_os = "linux")]
+async fn sdl2_download_url(client: &reqwest::Client) -> Result<(String, Option<String>), String> {
+    let arch = match std::env::consts::ARCH {
+        "x86" => "i686",
+        arch => arch,
+    };
+    let request = client
+        .get(format!(
+            "https://pypi.org/pypi/pysdl2-dll/{}/json",
+            PYSDL2_DLL_VERSION
+        ))
+        .header("User-Agent", "mineplace3d-launcher");
+    let response = get_with_retry(request, NETWORK_RETRIES, None)
+        .await
+        .map_err(|e| format!("Failed to look up the SDL2 build: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to look up the SDL2 build: {}",
+            response.status()
+        ));
+    }
+    let release: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse the SDL2 build list: {}", e))?;
+
+    release
+        .get("urls")
+        .and_then(|files| files.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|file| {
+            let file_name = file.get("filename")?.as_str()?;
+            if !(file_name.contains("manylinux") && file_name.ends_with(&format!("_{}.whl", arch)))
+            {
+                return None;
+            }
+            let url = file.get("url")?.as_str()?.to_string();
+            // A wheel without a digest can't be checked, so it isn't used at all
+            let sha256 = file.get("digests")?.get("sha256")?.as_str()?.to_string();
+            Some((url, Some(sha256)))
+        })
+        .next()
+        .ok_or_else(|| format!("No SDL2 build is available for Linux on {}", arch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    /// A manifest serving `contents` as version 0.3.0 for this machine from the mirror
+    /// directory `mirror`, listing `checksum` as its digest.
+    fn mirror_manifest(mirror: &Path, contents: &[u8], checksum: &str) -> Manifest {
+        let tag_dir = mirror.join("v0.3.0");
+        std::fs::create_dir_all(&tag_dir).unwrap();
+        std::fs::write(tag_dir.join("game-binary"), contents).unwrap();
+        let key = format!("{}_{}", utils::platform_os(), utils::platform_arch());
+        serde_json::from_value(serde_json::json!({
+            "latest": { "stable": "0.3.0", "beta": null, "alpha": null },
+            "base": reqwest::Url::from_directory_path(mirror).unwrap().to_string(),
+            "versions": {
+                "0.3.0": {
+                    "files": { key.clone(): "game-binary" },
+                    "checksums": { key: format!("sha256:{}", checksum) },
+                    "uploaded_on": "2024-01-01",
+                    "real_name": null
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    async fn fetch_from_mirror(game_dir: &Path, manifest: Manifest) -> Result<Version, String> {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let settings = LauncherSettings::new(game_dir.to_path_buf());
+        let client = build_client(&settings).unwrap();
+        let (progress_tx, _progress_rx) = iced::futures::channel::mpsc::channel(100);
+        fetch_version(
+            client,
+            manifest,
+            game_dir.to_path_buf(),
+            "0.3.0".parse().unwrap(),
+            DownloadOptions::from(&settings),
+            progress_tx,
+        )
+        .await
+    }
+
+    fn sha256_hex(data: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+
+        Sha256::digest(data)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    #[test]
+    fn download_path_is_a_part_file_in_the_staging_dir() {
+        let version = "0.3.0".parse().unwrap();
+        let game_dir = Path::new("/games/mineplace3d");
+        let name = format!("{}.part", version_file_name(version));
+        assert_eq!(
+            download_path(game_dir, None, version),
+            game_dir.join("versions").join(&name)
+        );
+        assert_eq!(
+            download_path(game_dir, Some(Path::new("/fast/staging")), version),
+            Path::new("/fast/staging").join(&name)
+        );
+    }
+
+    #[test]
+    fn sdl2_entries_skip_headers_and_add_ons() {
+        #[cfg(target_os = "linux")]
+        {
+            assert!(is_sdl2_entry("sdl2dll/dll/libSDL2-2.0.so.0"));
+            assert!(!is_sdl2_entry("sdl2dll/dll/libSDL2_ttf-2.0.so.0"));
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            assert!(is_sdl2_entry(&format!("lib/x64/{}", SDL2_FILE_NAME)));
+            assert!(!is_sdl2_entry("include/SDL.h"));
+        }
+        assert!(!is_sdl2_entry("README-SDL.txt"));
+    }
+
+    #[tokio::test]
+    async fn connection_closed_early_keeps_the_partial_file() {
+        let root = temp_dir("download-closed-early");
+        let path = root.join("game-binary.part");
+        let stream = iced::futures::stream::iter([Ok(bytes::Bytes::from_static(b"game"))]);
+        let (mut progress_tx, _progress_rx) = iced::futures::channel::mpsc::channel(100);
+
+        let error = download_to_file(
+            Some(11),
+            stream,
+            path.clone(),
+            0,
+            DownloadPhase::Game,
+            std::time::Duration::from_secs(5),
+            None,
+            &mut progress_tx,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.contains("Incomplete download"), "{}", error);
+        assert_eq!(std::fs::read(&path).unwrap(), b"game");
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_installs_a_version_from_a_local_mirror() {
+        let root = temp_dir("fetch-mirror");
+        let game_dir = root.join("game");
+        std::fs::create_dir_all(game_dir.join("versions")).unwrap();
+        let contents = b"game binary";
+        let manifest = mirror_manifest(&root.join("mirror"), contents, &sha256_hex(contents));
+
+        let version = fetch_from_mirror(&game_dir, manifest).await.unwrap();
+
+        let exec_path = game_dir.join("versions").join(version_file_name(version));
+        assert_eq!(std::fs::read(&exec_path).unwrap(), contents);
+        assert!(!download_path(&game_dir, None, version).exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_discards_a_download_with_the_wrong_checksum() {
+        let root = temp_dir("fetch-mirror-checksum");
+        let game_dir = root.join("game");
+        std::fs::create_dir_all(game_dir.join("versions")).unwrap();
+        let manifest = mirror_manifest(&root.join("mirror"), b"tampered", &sha256_hex(b"original"));
+
+        let error = fetch_from_mirror(&game_dir, manifest).await.unwrap_err();
+
+        assert!(error.contains("Checksum mismatch"), "{}", error);
+        let version = "0.3.0".parse().unwrap();
+        assert!(
+            !game_dir
+                .join("versions")
+                .join(version_file_name(version))
+                .exists()
+        );
+        assert!(!download_path(&game_dir, None, version).exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,226 @@
+//! The launcher's own log, written to a file next to the settings so problems can be diagnosed
+//! after the fact.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Size past which the log is rolled over to `launcher.log.1`
+const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Where the launcher log is written.
+pub fn log_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("mineplace3d-launcher")
+        .join("launcher.log")
+}
+
+/// Starts sending `tracing` events to the launcher log, which is rolled over whenever it grows
+/// past [`MAX_LOG_SIZE`]. Warnings and errors still go to stderr too.
+pub fn init() {
+    let path = log_path();
+    let file = match RollingFile::open(path.clone(), MAX_LOG_SIZE) {
+        Ok(file) => Some(Mutex::new(file)),
+        Err(e) => {
+            eprintln!("Failed to open launcher log {}: {}", path.display(), e);
+            None
+        }
+    };
+
+    let subscriber = FileSubscriber {
+        file,
+        next_span: AtomicU64::new(1),
+    };
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        eprintln!("The launcher log was already set up");
+    }
+}
+
+/// A log file that is moved aside to `<name>.1`, replacing the previous one, once it grows past
+/// `max_size`, so at most two logs' worth of disk space is used however long the launcher runs.
+struct RollingFile {
+    path: PathBuf,
+    file: std::fs::File,
+    /// Bytes in the current file
+    size: u64,
+    max_size: u64,
+}
+
+impl RollingFile {
+    fn open(path: PathBuf, max_size: u64) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = Self::open_file(&path)?;
+        let size = file.metadata()?.len();
+        let mut rolling = RollingFile {
+            path,
+            file,
+            size,
+            max_size,
+        };
+        if rolling.size > max_size {
+            rolling.roll()?;
+        }
+        Ok(rolling)
+    }
+
+    fn open_file(path: &std::path::Path) -> std::io::Result<std::fs::File> {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+    }
+
+    fn roll(&mut self) -> std::io::Result<()> {
+        let mut rolled_name = self.path.file_name().unwrap_or_default().to_os_string();
+        rolled_name.push(".1");
+        std::fs::rename(&self.path, self.path.with_file_name(rolled_name))?;
+        self.file = Self::open_file(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Appends `line` and a newline, rolling the file over first if the line would take it
+    /// past its maximum size.
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_size {
+            self.roll()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += len;
+        Ok(())
+    }
+}
+
+/// Writes one line per event; spans are accepted but not recorded.
+struct FileSubscriber {
+    file: Option<Mutex<RollingFile>>,
+    next_span: AtomicU64,
+}
+
+impl Subscriber for FileSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Dependencies are only interesting when something goes wrong
+        *metadata.level() <= Level::INFO
+            && (metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+                || *metadata.level() <= Level::WARN)
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut line = LineVisitor(String::new());
+        event.record(&mut line);
+        let level = *event.metadata().level();
+
+        if level <= Level::WARN {
+            eprintln!("{}", line.0.trim_start());
+        }
+        if let Some(file) = &self.file
+            && let Ok(mut file) = file.lock()
+        {
+            let _ = file.write_line(&format!(
+                "{} {:>5} {}:{}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                level,
+                event.metadata().target(),
+                line.0
+            ));
+        }
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Formats an event's message followed by its other fields as `name=value`.
+struct LineVisitor(String);
+
+impl Visit for LineVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        use std::fmt::Write;
+
+        let _ = if field.name() == "message" {
+            write!(self.0, " {:?}", value)
+        } else {
+            write!(self.0, " {}={:?}", field.name(), value)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn log_rolls_over_while_running() {
+        let dir = temp_dir("rolling-log");
+        let path = dir.join("launcher.log");
+        let mut log = RollingFile::open(path.clone(), 20).unwrap();
+
+        log.write_line("first line").unwrap();
+        log.write_line("second line").unwrap();
+        log.write_line("third line").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("launcher.log.1")).unwrap(),
+            "second line\n"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "third line\n");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn oversized_log_is_rolled_over_when_opened() {
+        let dir = temp_dir("rolling-log-open");
+        let path = dir.join("launcher.log");
+        std::fs::write(&path, "x".repeat(30)).unwrap();
+
+        let mut log = RollingFile::open(path.clone(), 20).unwrap();
+        log.write_line("fresh").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("launcher.log.1"))
+                .unwrap()
+                .len(),
+            30
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fresh\n");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn small_log_is_appended_to() {
+        let dir = temp_dir("rolling-log-append");
+        let path = dir.join("launcher.log");
+        std::fs::write(&path, "old\n").unwrap();
+
+        RollingFile::open(path.clone(), 100)
+            .unwrap()
+            .write_line("new")
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old\nnew\n");
+        assert!(!dir.join("launcher.log.1").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,79 +1,371 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::{collections::HashSet, path::PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use iced::futures::channel::mpsc::Sender;
 use iced::futures::{SinkExt, StreamExt};
 use iced::widget::*;
 use iced::widget::{button, column, container, row, text, text_input};
 use iced::{Subscription, Task};
 use serde::ser::SerializeStruct;
-use tokio::io::AsyncWriteExt;
 
-use crate::utils::{Manifest, bytes_to_human_readable, copy_dir};
-use crate::version::{Version, VersionChoice};
+use crate::download::{
+    DownloadEvent, DownloadOptions, DownloadPhase, DownloadUpdate, LatestRelease,
+};
+use crate::utils::{
+    InstalledVersion, Manifest, VersionsFile, bytes_to_human_readable, copy_dir, format_eta,
+    version_file_name,
+};
+use crate::version::{Version, VersionChoice, VersionStage, newer_build_of};
 
+mod cli;
+mod download;
+mod logging;
+#[cfg(test)]
+mod test_util;
 mod utils;
 mod version;
 
+/// How long an error stays on screen before it is cleared
+const ERROR_DISPLAY_TIME: std::time::Duration = std::time::Duration::from_secs(6);
+
+/// How long a confirmation like "Copied!" stays on screen
+const NOTICE_DISPLAY_TIME: std::time::Duration = std::time::Duration::from_secs(2);
+
+const DEFAULT_RELEASES_REPO: &str = "Muhtasim-Rasheed/mineplace3d";
+
+/// The repository the launcher itself is released from
+const LAUNCHER_REPO: &str = "Muhtasim-Rasheed/mineplace3d-launcher";
+
+const DEFAULT_THEME: Theme = Theme::CatppuccinMocha;
+
+/// Text size the launcher's layout is designed around; other sizes scale the whole window
+const DEFAULT_FONT_SIZE: u16 = 16;
+
+const FONT_SIZES: [u16; 6] = [12, 14, 16, 18, 20, 24];
+
+/// How many parts a large download can be split into, where 1 downloads in a single stream
+const DOWNLOAD_SEGMENTS: [u8; 4] = [1, 2, 4, 8];
+
+/// How long the versions directory has to stay unchanged before the installed list is reloaded
+const VERSIONS_WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// How long a connection may take to open or go without receiving data, in seconds
+const DEFAULT_NETWORK_TIMEOUT_SECS: u64 = 15;
+
 #[derive(Debug, Clone)]
 enum ButtonMessage {
     DownloadVersion,
+    UpdateToVersion(Version),
+    CancelDownload,
+    RetryDownload,
     RunVersion,
+    PlayLatest,
+    UninstallVersion(Version),
+    RefreshVersions,
+    InstallFromFile,
+    VerifyVersion(Version),
+    CopyVersion(Version),
+    ToggleFavorite(Version),
+    CopyDiagnostics,
+    ReinstallVersion(Version),
+    RedownloadVersion(Version),
+    CancelRedownload,
+    RemoveAllVersions,
+    ConfirmRemoveAllVersions,
+    CancelRemoveAllVersions,
+    ResetSettings,
+    ConfirmResetSettings,
+    CancelResetSettings,
+    ShowChangelog(Version),
+    CloseChangelog,
+    OpenLauncherRelease,
+    DismissLauncherUpdate,
+    ConfirmLocalInstall,
+    CancelLocalInstall,
 
     SwitchTab(View),
+    FinishFirstRun,
+    OpenGameFolder,
+    OpenLatestLog,
+    OpenLauncherLog,
+    BrowseGameDir,
     SaveSettings,
+    ExportSettings,
+    AddProfile,
+    RemoveProfile,
+    ImportSettings,
+    ReinstallSdl2,
+    MeasureGameDir,
+    ConfirmGameDirMove,
+    CancelGameDirMove,
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 enum InputMessage {
     GameDirContentChanged(String),
+    LaunchArgsContentChanged(String),
+    LaunchWrapperContentChanged(String),
+    GithubTokenContentChanged(String),
+    HttpProxyContentChanged(String),
+    NetworkTimeoutContentChanged(String),
+    MaxDownloadSpeedContentChanged(String),
+    DownloadStagingDirContentChanged(String),
+    ReleasesRepoContentChanged(String),
+    FilterChanged(String),
+    LocalVersionContentChanged(String),
+    ProfileNameContentChanged(String),
+}
+
+#[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
+enum CheckboxMessage {
+    VerifyChecksumsToggled(bool),
+    AutoInstallSdl2Toggled(bool),
+    BundleSdl2Toggled(bool),
+    RemoveOldGameDirToggled(bool),
+    OfflineToggled(bool),
+    CheckLauncherUpdatesToggled(bool),
+    ChannelToggled(VersionStage, bool),
+    CleanRunToggled(bool),
+    KeepCleanGameDirToggled(bool),
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 enum PickListMessage {
     SelectedPlayingVersion(VersionChoice),
     SelectedDownloadVersion(VersionChoice),
+    SelectedInstalledSort(InstalledSort),
+    SelectedTheme(Theme),
+    SelectedFont(FontChoice),
+    SelectedFontSize(u16),
+    SelectedDownloadSegments(u8),
+    SelectedProfile(String),
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     Button(ButtonMessage),
     Input(InputMessage),
+    Checkbox(CheckboxMessage),
     PickList(PickListMessage),
     ManifestFetched(Manifest),
     ManifestFetchFailed(String),
     VersionDownloaded(Version),
     VersionDownloadFailed(String),
-    VersionDownloadUpdateReady(Sender<Message>),
     VersionDownloadUpdate(DownloadUpdate),
     VersionDownloadClear,
+    Sdl2Installed(Result<(), String>),
+    PollRunningGames,
+    VersionsChanged,
+    ErrorExpired(std::time::Instant),
+    NoticeExpired(std::time::Instant),
+    KeyPressed(iced::keyboard::Key, iced::keyboard::Modifiers),
+    GameDirMoveProgress(f32),
+    GameDirMeasured(PathBuf, Result<u64, String>),
+    GameDirMoved(Result<PathBuf, String>),
+    OldGameDirRemoved(Result<(), String>),
+    CleanGameDirRemoved(Result<(), String>),
+    VersionVerified(Version, Result<bool, String>),
+    ReleaseNotesFetched(Version, Result<String, String>),
+    LauncherReleaseFetched(Result<LatestRelease, String>),
+    LocalBinaryPicked(PathBuf),
+    SettingsExportPicked(PathBuf),
+    SettingsImportPicked(PathBuf),
+    LocalBinaryInstalled(Result<Version, String>),
+    WindowResized(iced::Size),
+    WindowCloseRequested(iced::window::Id),
 }
 
-#[derive(Debug, Default, Clone)]
-enum DownloadUpdate {
-    Progress {
-        progress: f32,
-        /// Bytes per second
-        speed: f32,
-    },
-    Finished,
-    Failed {
-        last_progress: Option<f32>,
-    },
-    #[default]
-    None,
+/// A game directory move that has been requested but not yet confirmed.
+#[derive(Debug, Clone)]
+struct PendingGameDirMove {
+    from: PathBuf,
+    to: PathBuf,
+    files: u64,
+    bytes: u64,
 }
 
-impl DownloadUpdate {
-    fn new(progress: f32, speed: f32) -> Self {
-        Self::Progress { progress, speed }
-    }
+/// A named setup with a game directory of its own.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Profile {
+    name: String,
+    game_dir: PathBuf,
+}
+
+/// Name of the profile the game directory from before profiles existed is kept under
+const DEFAULT_PROFILE_NAME: &str = "Default";
+
+/// A game directory in `parent` for a new profile called `name`, named after it like
+/// `mineplace3d-my-game`. Names that come out the same, like "My Game" and "My-Game", get a
+/// numbered suffix so no two profiles, or a profile and an existing directory, share one.
+fn unique_profile_dir(parent: &Path, name: &str, profiles: &[Profile]) -> PathBuf {
+    let dir_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let base = format!("mineplace3d-{}", dir_name);
+    (1..)
+        .map(|n| match n {
+            1 => parent.join(&base),
+            n => parent.join(format!("{}-{}", base, n)),
+        })
+        .find(|dir| !dir.exists() && !profiles.iter().any(|profile| profile.game_dir == *dir))
+        .expect("There is always an unused directory name")
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct LauncherSettings {
+    /// The game directory of the active profile
     game_dir: PathBuf,
+    profiles: Vec<Profile>,
+    /// Name of the profile in `profiles` whose game directory is in use
+    active_profile: String,
+    /// Whether downloaded binaries are checked against the manifest's SHA-256 checksums
+    verify_checksums: bool,
+    /// The version picked on the play tab, or `None` for the latest one
+    last_selected_version: Option<Version>,
+    window_size: Option<(u32, u32)>,
+    /// Extra arguments passed to the game when it is launched
+    launch_args: Vec<String>,
+    /// Program the game is started through on Linux, like `gamemoderun`, with its arguments
+    launch_wrapper: Option<String>,
+    /// Sent to GitHub to lift the unauthenticated rate limit
+    github_token: Option<String>,
+    /// The `owner/name` GitHub repository whose Pages site hosts the manifest
+    releases_repo: String,
+    /// Whether SDL2.dll is downloaded automatically on Windows
+    auto_install_sdl2: bool,
+    /// Whether SDL2 is downloaded next to the game binaries on Linux instead of using the
+    /// system's
+    bundle_sdl2: bool,
+    theme: Theme,
+    /// The font all text is drawn in, which takes effect when the launcher is restarted
+    font: FontChoice,
+    /// Base text size, which the whole interface is scaled to
+    font_size: u16,
+    /// Proxy all launcher traffic goes through, like `http://proxy.example:8080`
+    http_proxy: Option<String>,
+    /// Connect and read timeout for network requests, in seconds
+    network_timeout_secs: u64,
+    /// Cap on the download speed in bytes per second, or `None` for unlimited
+    max_download_bytes_per_sec: Option<u64>,
+    /// How many parts large downloads are split into and fetched at once
+    download_segments: u8,
+    /// Where binaries are downloaded before being moved into the versions directory, when
+    /// that shouldn't happen inside the game directory
+    download_staging_dir: Option<PathBuf>,
+    /// Whether the launcher stays away from the network, leaving only installed versions
+    offline: bool,
+    /// Whether a newer launcher release is looked for on startup
+    check_launcher_updates: bool,
+    /// Release channels whose versions are listed and offered for download
+    channels: HashSet<VersionStage>,
+    /// Versions pinned above the rest of the installed list, whatever it is sorted by
+    favorite_versions: HashSet<Version>,
+}
+
+impl LauncherSettings {
+    /// Settings with a single default profile using `game_dir`.
+    fn new(game_dir: PathBuf) -> Self {
+        LauncherSettings {
+            profiles: vec![Profile {
+                name: DEFAULT_PROFILE_NAME.to_string(),
+                game_dir: game_dir.clone(),
+            }],
+            active_profile: DEFAULT_PROFILE_NAME.to_string(),
+            game_dir,
+            verify_checksums: true,
+            last_selected_version: None,
+            window_size: None,
+            launch_args: Vec::new(),
+            launch_wrapper: None,
+            github_token: None,
+            releases_repo: DEFAULT_RELEASES_REPO.to_string(),
+            auto_install_sdl2: true,
+            bundle_sdl2: false,
+            theme: DEFAULT_THEME,
+            font: FontChoice::default(),
+            font_size: DEFAULT_FONT_SIZE,
+            http_proxy: None,
+            network_timeout_secs: DEFAULT_NETWORK_TIMEOUT_SECS,
+            max_download_bytes_per_sec: None,
+            download_segments: 1,
+            download_staging_dir: None,
+            offline: false,
+            check_launcher_updates: true,
+            channels: VersionStage::ALL.into_iter().collect(),
+            favorite_versions: HashSet::new(),
+        }
+    }
+
+    /// Where the game directory goes unless another one is picked, relative to the working
+    /// directory on platforms without a data directory.
+    fn default_game_dir() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("mineplace3d")
+    }
+
+    /// Changes the game directory of the active profile.
+    fn set_game_dir(&mut self, game_dir: PathBuf) {
+        if let Some(profile) = self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.name == self.active_profile)
+        {
+            profile.game_dir = game_dir.clone();
+        }
+        self.game_dir = game_dir;
+    }
+
+    fn network_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.network_timeout_secs)
+    }
+
+    fn file_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("mineplace3d-launcher")
+            .join("launcher_settings.json")
+    }
+
+    /// Reads the settings file, returning `Ok(None)` if it does not exist yet.
+    fn load() -> Result<Option<Self>, String> {
+        let settings_file = Self::file_path();
+        if !settings_file.exists() {
+            return Ok(None);
+        }
+        let config_data = std::fs::read_to_string(&settings_file)
+            .map_err(|e| format!("Failed to read launcher configuration file: {}", e))?;
+        serde_json::from_str(&config_data)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse launcher configuration file: {}", e))
+    }
+
+    /// Moves an unreadable settings file aside to `launcher_settings.json.bak` so it is not
+    /// overwritten by the defaults.
+    fn back_up_file() -> std::io::Result<PathBuf> {
+        let settings_file = Self::file_path();
+        let backup_file = settings_file.with_extension("json.bak");
+        std::fs::rename(&settings_file, &backup_file)?;
+        Ok(backup_file)
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let settings_file = Self::file_path();
+        let settings_data = serde_json::to_string_pretty(self)?;
+        std::fs::create_dir_all(settings_file.parent().unwrap())?;
+        utils::write_atomic(&settings_file, settings_data)
+    }
 }
 
 impl serde::Serialize for LauncherSettings {
@@ -81,12 +373,51 @@ impl serde::Serialize for LauncherSettings {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct("LauncherSettings", 1)?;
+        let mut state = serializer.serialize_struct("LauncherSettings", 24)?;
         state.serialize_field("game_dir", self.game_dir.to_str().unwrap())?;
+        state.serialize_field("profiles", &self.profiles)?;
+        state.serialize_field("active_profile", &self.active_profile)?;
+        state.serialize_field("verify_checksums", &self.verify_checksums)?;
+        state.serialize_field("last_selected_version", &self.last_selected_version)?;
+        state.serialize_field("window_size", &self.window_size)?;
+        state.serialize_field("launch_args", &self.launch_args)?;
+        state.serialize_field("launch_wrapper", &self.launch_wrapper)?;
+        state.serialize_field("github_token", &self.github_token)?;
+        state.serialize_field("releases_repo", &self.releases_repo)?;
+        state.serialize_field("auto_install_sdl2", &self.auto_install_sdl2)?;
+        state.serialize_field("bundle_sdl2", &self.bundle_sdl2)?;
+        state.serialize_field("theme", &self.theme.to_string())?;
+        state.serialize_field("font", &self.font.to_string())?;
+        state.serialize_field("font_size", &self.font_size)?;
+        state.serialize_field("http_proxy", &self.http_proxy)?;
+        state.serialize_field("network_timeout_secs", &self.network_timeout_secs)?;
+        state.serialize_field(
+            "max_download_bytes_per_sec",
+            &self.max_download_bytes_per_sec,
+        )?;
+        state.serialize_field("download_segments", &self.download_segments)?;
+        state.serialize_field("download_staging_dir", &self.download_staging_dir)?;
+        state.serialize_field("offline", &self.offline)?;
+        state.serialize_field("check_launcher_updates", &self.check_launcher_updates)?;
+        let channels: Vec<String> = VersionStage::ALL
+            .iter()
+            .filter(|stage| self.channels.contains(stage))
+            .map(|stage| stage.to_string())
+            .collect();
+        state.serialize_field("channels", &channels)?;
+        let mut favorite_versions: Vec<&Version> = self.favorite_versions.iter().collect();
+        favorite_versions.sort();
+        state.serialize_field("favorite_versions", &favorite_versions)?;
         state.end()
     }
 }
 
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        LauncherSettings::new(LauncherSettings::default_game_dir())
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for LauncherSettings {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -97,10 +428,143 @@ impl<'de> serde::Deserialize<'de> for LauncherSettings {
             .get("game_dir")
             .and_then(|v| v.as_str())
             .map(PathBuf::from)
-            .or_else(|| dirs::data_dir().map(|data_dir| data_dir.join("mineplace3d")))
-            .ok_or_else(|| serde::de::Error::custom("game_dir is required"))?;
+            .unwrap_or_else(LauncherSettings::default_game_dir);
+        // Only the keys present override the defaults, so older files keep loading
+        let mut settings = LauncherSettings::new(game_dir);
+        // Settings from before profiles existed become a single default profile
+        if let Some(profiles) = helper
+            .get("profiles")
+            .and_then(|v| serde_json::from_value::<Vec<Profile>>(v.clone()).ok())
+            .filter(|profiles| !profiles.is_empty())
+        {
+            settings.profiles = profiles;
+        }
+        settings.active_profile = helper
+            .get("active_profile")
+            .and_then(|v| v.as_str())
+            .filter(|name| {
+                settings
+                    .profiles
+                    .iter()
+                    .any(|profile| profile.name == *name)
+            })
+            .unwrap_or(&settings.profiles[0].name)
+            .to_string();
+        // `game_dir` is kept in sync with the active profile, but the profile wins if they differ
+        if let Some(profile) = settings
+            .profiles
+            .iter()
+            .find(|profile| profile.name == settings.active_profile)
+        {
+            settings.game_dir = profile.game_dir.clone();
+        }
+        if let Some(verify_checksums) = helper.get("verify_checksums").and_then(|v| v.as_bool()) {
+            settings.verify_checksums = verify_checksums;
+        }
+        if let Some(v) = helper.get("last_selected_version") {
+            settings.last_selected_version = v.as_str().and_then(|v| v.parse().ok());
+        }
+        if let Some(v) = helper.get("window_size") {
+            settings.window_size = serde_json::from_value(v.clone()).ok();
+        }
+        if let Some(launch_args) = helper
+            .get("launch_args")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+        {
+            settings.launch_args = launch_args;
+        }
+        if let Some(v) = helper.get("launch_wrapper") {
+            settings.launch_wrapper = v.as_str().map(str::to_string);
+        }
+        if let Some(v) = helper.get("github_token") {
+            settings.github_token = v.as_str().map(str::to_string);
+        }
+        if let Some(releases_repo) = helper.get("releases_repo").and_then(|v| v.as_str()) {
+            settings.releases_repo = releases_repo.to_string();
+        }
+        if let Some(auto_install_sdl2) = helper.get("auto_install_sdl2").and_then(|v| v.as_bool()) {
+            settings.auto_install_sdl2 = auto_install_sdl2;
+        }
+        if let Some(bundle_sdl2) = helper.get("bundle_sdl2").and_then(|v| v.as_bool()) {
+            settings.bundle_sdl2 = bundle_sdl2;
+        }
+        if let Some(theme) = helper
+            .get("theme")
+            .and_then(|v| v.as_str())
+            .and_then(|name| Theme::ALL.iter().find(|theme| theme.to_string() == name))
+        {
+            settings.theme = theme.clone();
+        }
+        if let Some(font) = helper
+            .get("font")
+            .and_then(|v| v.as_str())
+            .and_then(|name| {
+                FontChoice::ALL
+                    .into_iter()
+                    .find(|font| font.to_string() == name)
+            })
+        {
+            settings.font = font;
+        }
+        if let Some(font_size) = helper
+            .get("font_size")
+            .and_then(|v| v.as_u64())
+            .and_then(|size| u16::try_from(size).ok())
+            .filter(|size| FONT_SIZES.contains(size))
+        {
+            settings.font_size = font_size;
+        }
+        if let Some(v) = helper.get("http_proxy") {
+            settings.http_proxy = v.as_str().map(str::to_string);
+        }
+        if let Some(network_timeout_secs) = helper
+            .get("network_timeout_secs")
+            .and_then(|v| v.as_u64())
+            .filter(|secs| *secs > 0)
+        {
+            settings.network_timeout_secs = network_timeout_secs;
+        }
+        if let Some(v) = helper.get("max_download_bytes_per_sec") {
+            settings.max_download_bytes_per_sec = v.as_u64().filter(|limit| *limit > 0);
+        }
+        if let Some(download_segments) = helper
+            .get("download_segments")
+            .and_then(|v| v.as_u64())
+            .and_then(|segments| u8::try_from(segments).ok())
+            .filter(|segments| DOWNLOAD_SEGMENTS.contains(segments))
+        {
+            settings.download_segments = download_segments;
+        }
+        if let Some(v) = helper.get("download_staging_dir") {
+            settings.download_staging_dir = v.as_str().map(PathBuf::from);
+        }
+        if let Some(offline) = helper.get("offline").and_then(|v| v.as_bool()) {
+            settings.offline = offline;
+        }
+        if let Some(check_launcher_updates) = helper
+            .get("check_launcher_updates")
+            .and_then(|v| v.as_bool())
+        {
+            settings.check_launcher_updates = check_launcher_updates;
+        }
+        if let Some(names) = helper.get("channels").and_then(|v| v.as_array()) {
+            settings.channels = VersionStage::ALL
+                .into_iter()
+                .filter(|stage| {
+                    names
+                        .iter()
+                        .any(|name| name.as_str() == Some(&stage.to_string()))
+                })
+                .collect();
+        }
+        if let Some(versions) = helper.get("favorite_versions").and_then(|v| v.as_array()) {
+            settings.favorite_versions = versions
+                .iter()
+                .filter_map(|v| v.as_str()?.parse().ok())
+                .collect();
+        }
 
-        Ok(LauncherSettings { game_dir })
+        Ok(settings)
     }
 }
 
@@ -109,9 +573,14 @@ enum View {
     Play,
     Download,
     Settings,
+    /// Reached from the settings rather than the tab bar
+    About,
+    /// Shown instead of the tabs until a new install's game directory is confirmed
+    FirstRun,
 }
 
 impl View {
+    /// The views that have a tab
     const ALL: [View; 3] = [View::Play, View::Download, View::Settings];
 }
 
@@ -121,6 +590,76 @@ impl std::fmt::Display for View {
             View::Play => write!(f, "Play"),
             View::Download => write!(f, "Download"),
             View::Settings => write!(f, "Settings"),
+            View::About => write!(f, "About"),
+            View::FirstRun => write!(f, "Welcome"),
+        }
+    }
+}
+
+/// The typeface the launcher draws its text in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum FontChoice {
+    #[default]
+    Monospace,
+    SansSerif,
+    Serif,
+}
+
+impl FontChoice {
+    const ALL: [FontChoice; 3] = [
+        FontChoice::Monospace,
+        FontChoice::SansSerif,
+        FontChoice::Serif,
+    ];
+
+    fn font(self) -> iced::Font {
+        match self {
+            FontChoice::Monospace => iced::Font::MONOSPACE,
+            FontChoice::SansSerif => iced::Font::DEFAULT,
+            FontChoice::Serif => iced::Font {
+                family: iced::font::Family::Serif,
+                ..iced::Font::DEFAULT
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for FontChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontChoice::Monospace => write!(f, "Monospace"),
+            FontChoice::SansSerif => write!(f, "Sans-serif"),
+            FontChoice::Serif => write!(f, "Serif"),
+        }
+    }
+}
+
+/// How the installed versions list on the play tab is ordered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum InstalledSort {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+    RecentlyInstalled,
+    RecentlyPlayed,
+}
+
+impl InstalledSort {
+    const ALL: [InstalledSort; 4] = [
+        InstalledSort::NewestFirst,
+        InstalledSort::OldestFirst,
+        InstalledSort::RecentlyInstalled,
+        InstalledSort::RecentlyPlayed,
+    ];
+}
+
+impl std::fmt::Display for InstalledSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstalledSort::NewestFirst => write!(f, "Version ↓"),
+            InstalledSort::OldestFirst => write!(f, "Version ↑"),
+            InstalledSort::RecentlyInstalled => write!(f, "Recently installed"),
+            InstalledSort::RecentlyPlayed => write!(f, "Recently played"),
         }
     }
 }
@@ -129,400 +668,1363 @@ struct Launcher {
     launcher_settings: LauncherSettings,
     manifest: Option<Manifest>,
     versions: HashSet<Version>,
+    /// Install details for the versions in `versions`, as stored in `versions.json`
+    version_records: HashMap<Version, InstalledVersion>,
+    installed_sort: InstalledSort,
     chosen_playing_version: VersionChoice,
     chosen_download_version: VersionChoice,
     input_game_dir_content: String,
+    input_launch_args_content: String,
+    input_launch_wrapper_content: String,
+    input_github_token_content: String,
+    input_http_proxy_content: String,
+    input_network_timeout_content: String,
+    /// Download speed limit in KiB/s, empty for unlimited
+    input_max_download_speed_content: String,
+    input_download_staging_dir_content: String,
+    input_releases_repo_content: String,
+    input_filter_content: String,
+    /// Version typed into the install-from-file dialog
+    input_local_version_content: String,
+    /// Name typed for a new profile
+    input_profile_name_content: String,
+    /// A local binary picked for installing, waiting for its version to be confirmed
+    pending_local_install: Option<PathBuf>,
+    /// Versions whose installation is being verified
+    verifying_versions: HashSet<Version>,
+    /// Versions whose installation passed verification since they were installed
+    verified_versions: HashSet<Version>,
+    /// A version that failed verification, waiting for the user to decide on re-downloading it
+    corrupt_version: Option<Version>,
+    /// Whether removing every installed version is waiting for confirmation
+    confirm_remove_all_versions: bool,
+    /// Whether restoring the default settings is waiting for confirmation
+    confirm_reset_settings: bool,
+    /// The version whose changelog is open
+    changelog_version: Option<Version>,
+    /// Release notes fetched so far, as plain text
+    release_notes: HashMap<Version, String>,
+    /// A newer launcher release and the URL of its page, until it is dismissed
+    launcher_update: Option<(Version, String)>,
     version_downloading: bool,
+    /// Whether Download was pressed before the manifest arrived, to start once it does
+    download_queued: bool,
     version_download_update: DownloadUpdate,
-    version_update_sender: Option<Sender<Message>>,
+    /// The version currently being downloaded and a handle to abort its task
+    version_download_task: Option<(Version, iced::task::Handle)>,
+    /// The version the last download was for, so a failed one can be retried
+    last_download_attempt: Option<Version>,
+    /// A handle to abort an SDL2 reinstall started from the settings
+    sdl2_install_task: Option<iced::task::Handle>,
+    last_error: Option<(String, std::time::Instant)>,
+    /// The most recent error, kept after it stops being shown so it can go in diagnostics
+    latest_error: Option<String>,
+    /// Short confirmation shown in place of an error, with when it was shown
+    last_notice: Option<(String, std::time::Instant)>,
+    /// A game directory move waiting for the user to confirm it
+    pending_game_dir_move: Option<PendingGameDirMove>,
+    /// Whether the old game directory is deleted once a move has been copied over
+    remove_old_game_dir: bool,
+    /// Progress of copying the game directory to a new location, if one is running
+    game_dir_move_progress: Option<f32>,
+    /// Total size of the game directory it was measured for, since walking it is slow
+    game_dir_size: Option<(PathBuf, u64)>,
+    measuring_game_dir: bool,
+    /// Game processes started by the launcher that haven't exited yet, with when they started
+    running_games: HashMap<Version, (std::process::Child, std::time::Instant)>,
+    /// File managers and browsers opened by the launcher, waited on once they exit
+    opened_processes: Vec<std::process::Child>,
+    /// Whether games are started against a throwaway game directory instead of the real one
+    clean_run: bool,
+    /// Whether a throwaway game directory is kept after its game exits, for inspection
+    keep_clean_game_dir: bool,
+    /// Running games started with a throwaway game directory, and whether it is kept afterwards
+    clean_runs: HashMap<Version, (PathBuf, bool)>,
+    /// Total time spent in each version, as stored in `play_times.json`
+    play_times: HashMap<Version, std::time::Duration>,
+    /// Set when the manifest could not be fetched, which usually means there is no network
+    network_unavailable: bool,
     view: View,
 }
 
 impl Launcher {
     fn new() -> (Self, Task<Message>) {
-        let launcher_settings_file = dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("mineplace3d-launcher")
-            .join("launcher_settings.json");
-        let launcher_settings = if launcher_settings_file.exists() {
-            let config_data = std::fs::read_to_string(launcher_settings_file)
-                .expect("Failed to read launcher configuration file");
-            serde_json::from_str(&config_data).expect("Failed to parse launcher configuration file")
-        } else {
-            LauncherSettings {
-                game_dir: dirs::data_dir()
-                    .map(|data_dir| data_dir.join("mineplace3d"))
-                    .expect("Failed to determine default game directory"),
-            }
+        let mut startup_error = None;
+        let loaded_settings = LauncherSettings::load().unwrap_or_else(|e| {
+            startup_error = Some(match LauncherSettings::back_up_file() {
+                Ok(backup_file) => format!(
+                    "{}. Using default settings; the old file was saved as {}",
+                    e,
+                    backup_file.display()
+                ),
+                Err(backup_error) => format!(
+                    "{}. Using default settings; failed to back up the old file: {}",
+                    e, backup_error
+                ),
+            });
+            None
+        });
+        // Nothing is created on a fresh install until the game directory is confirmed
+        let first_run = loaded_settings.is_none() && startup_error.is_none();
+        let launcher_settings = loaded_settings.unwrap_or_default();
+
+        let first_run = first_run && !launcher_settings.game_dir.exists();
+        if !first_run && let Err(e) = Self::setup_folder_structure(&launcher_settings.game_dir) {
+            startup_error = Some(format!(
+                "Failed to set up game directory {}: {}",
+                launcher_settings.game_dir.display(),
+                e
+            ));
+        }
+
+        let mut launcher = Self::with_settings(launcher_settings, first_run);
+
+        let show_versions_error = launcher.load_versions();
+
+        if let Some(version) = launcher.launcher_settings.last_selected_version
+            && launcher.versions.contains(&version)
+        {
+            launcher.chosen_playing_version = VersionChoice::Specific(version);
+        }
+
+        let fetch_manifest = launcher.refetch_manifest();
+        let check_launcher_update = launcher.check_launcher_update();
+
+        let restore_window_size = match launcher.launcher_settings.window_size {
+            Some((width, height)) => iced::window::oldest().and_then(move |id| {
+                iced::window::resize(id, iced::Size::new(width as f32, height as f32))
+            }),
+            None => Task::none(),
+        };
+
+        let show_startup_error = match startup_error {
+            Some(error) => launcher.show_error(error),
+            None => Task::none(),
         };
 
-        Self::setup_folder_structure(&launcher_settings.game_dir);
+        (
+            launcher,
+            Task::batch([
+                fetch_manifest,
+                check_launcher_update,
+                restore_window_size,
+                show_startup_error,
+                show_versions_error,
+            ]),
+        )
+    }
 
+    /// A launcher for `launcher_settings` with nothing loaded yet.
+    fn with_settings(launcher_settings: LauncherSettings, first_run: bool) -> Self {
         let game_dir = launcher_settings.game_dir.clone();
+        let launch_args = launcher_settings.launch_args.clone();
+        let launch_wrapper = launcher_settings.launch_wrapper.clone();
+        let github_token = launcher_settings.github_token.clone();
+        let http_proxy = launcher_settings.http_proxy.clone();
+        let network_timeout_secs = launcher_settings.network_timeout_secs;
+        let max_download_bytes_per_sec = launcher_settings.max_download_bytes_per_sec;
+        let download_staging_dir = launcher_settings.download_staging_dir.clone();
+        let releases_repo = launcher_settings.releases_repo.clone();
 
-        let mut launcher = Self {
+        Self {
             launcher_settings,
             manifest: None,
             versions: HashSet::new(),
+            version_records: HashMap::new(),
+            installed_sort: InstalledSort::default(),
             chosen_playing_version: VersionChoice::Latest,
             chosen_download_version: VersionChoice::Latest,
             input_game_dir_content: game_dir.to_string_lossy().to_string(),
+            input_launch_args_content: utils::join_args(&launch_args),
+            input_launch_wrapper_content: launch_wrapper.unwrap_or_default(),
+            input_github_token_content: github_token.unwrap_or_default(),
+            input_http_proxy_content: http_proxy.unwrap_or_default(),
+            input_network_timeout_content: network_timeout_secs.to_string(),
+            input_max_download_speed_content: max_download_bytes_per_sec
+                .map(|limit| (limit / 1024).to_string())
+                .unwrap_or_default(),
+            input_download_staging_dir_content: download_staging_dir
+                .map(|dir| dir.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            input_releases_repo_content: releases_repo,
+            input_filter_content: String::new(),
+            input_local_version_content: String::new(),
+            input_profile_name_content: String::new(),
+            pending_local_install: None,
+            verifying_versions: HashSet::new(),
+            verified_versions: HashSet::new(),
+            corrupt_version: None,
+            confirm_remove_all_versions: false,
+            confirm_reset_settings: false,
+            changelog_version: None,
+            release_notes: HashMap::new(),
+            launcher_update: None,
             version_downloading: false,
+            download_queued: false,
             version_download_update: DownloadUpdate::default(),
-            version_update_sender: None,
-            view: View::Play,
-        };
+            version_download_task: None,
+            last_download_attempt: None,
+            sdl2_install_task: None,
+            last_error: None,
+            latest_error: None,
+            last_notice: None,
+            pending_game_dir_move: None,
+            remove_old_game_dir: false,
+            game_dir_move_progress: None,
+            game_dir_size: None,
+            measuring_game_dir: false,
+            running_games: HashMap::new(),
+            opened_processes: Vec::new(),
+            clean_run: false,
+            keep_clean_game_dir: false,
+            clean_runs: HashMap::new(),
+            play_times: HashMap::new(),
+            network_unavailable: false,
+            view: if first_run {
+                View::FirstRun
+            } else {
+                View::Play
+            },
+        }
+    }
 
-        launcher.load_versions();
+    fn setup_folder_structure(game_dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(game_dir)?;
+        std::fs::create_dir_all(game_dir.join("versions"))?;
+        std::fs::create_dir_all(game_dir.join("logs"))
+    }
 
-        (
-            launcher,
-            Task::perform(Self::fetch_manifest(), |m| match m {
+    /// Creates the game directory picked on the first-run screen and saves it, so the next start
+    /// goes straight to the tabs.
+    fn finish_first_run(&mut self, game_dir: PathBuf) -> Result<(), String> {
+        if !game_dir.is_absolute() {
+            return Err(format!(
+                "Game directory must be an absolute path, got {}",
+                game_dir.display()
+            ));
+        }
+        Self::setup_folder_structure(&game_dir).map_err(|e| {
+            format!(
+                "Failed to set up game directory {}: {}",
+                game_dir.display(),
+                e
+            )
+        })?;
+
+        self.launcher_settings.set_game_dir(game_dir);
+        self.input_game_dir_content = self
+            .launcher_settings
+            .game_dir
+            .to_string_lossy()
+            .to_string();
+        self.save_launcher_settings().map_err(|e| {
+            format!(
+                "Can't write to {}: {}",
+                LauncherSettings::file_path().display(),
+                e
+            )
+        })?;
+        self.view = View::Play;
+        Ok(())
+    }
+
+    /// Whether downloads are unavailable, either by choice or because the network is down.
+    fn is_offline(&self) -> bool {
+        self.launcher_settings.offline || self.network_unavailable
+    }
+
+    /// Whether `version` belongs to one of the release channels picked in the settings.
+    fn channel_enabled(&self, version: Version) -> bool {
+        self.launcher_settings.channels.contains(&version.stage())
+    }
+
+    fn refetch_manifest(&self) -> Task<Message> {
+        if self.launcher_settings.offline {
+            return Task::none();
+        }
+        let client = match download::build_client(&self.launcher_settings) {
+            Ok(client) => client,
+            Err(e) => return Task::done(Message::ManifestFetchFailed(e)),
+        };
+        Task::perform(
+            download::fetch_manifest(client, self.launcher_settings.releases_repo.clone()),
+            |m| match m {
                 Ok(m) => Message::ManifestFetched(m),
                 Err(e) => Message::ManifestFetchFailed(e),
-            }),
+            },
         )
     }
 
-    fn setup_folder_structure(game_dir: &PathBuf) {
-        std::fs::create_dir_all(game_dir).expect("Failed to create game directory");
-        std::fs::create_dir_all(game_dir.join("versions"))
-            .expect("Failed to create versions directory");
+    /// Deletes every version binary in the versions directory, leaving SDL2 and the
+    /// launcher's own files, and reports how much space was freed.
+    fn remove_all_versions(&mut self) -> Task<Message> {
+        let versions_dir = self.launcher_settings.game_dir.join("versions");
+        let entries = match std::fs::read_dir(&versions_dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                return self.show_error(format!(
+                    "Failed to read {}: {}",
+                    versions_dir.display(),
+                    e
+                ));
+            }
+        };
+
+        let mut removed = 0;
+        let mut freed = 0;
+        let mut failed = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(version) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(utils::version_from_file_name)
+            else {
+                continue;
+            };
+            // macOS app bundles are directories, everything else is a single file
+            let (size, result) = if path.is_dir() {
+                (
+                    utils::dir_size(&path).unwrap_or(0),
+                    std::fs::remove_dir_all(&path),
+                )
+            } else {
+                (
+                    entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    std::fs::remove_file(&path),
+                )
+            };
+            match result {
+                Ok(()) => {
+                    removed += 1;
+                    freed += size;
+                    self.versions.remove(&version);
+                    self.version_records.remove(&version);
+                }
+                Err(e) => failed.push(format!("v{}: {}", version, e)),
+            }
+        }
+
+        // Records of versions whose files were already gone go too
+        if failed.is_empty() {
+            self.versions.clear();
+            self.version_records.clear();
+        }
+        self.chosen_playing_version = VersionChoice::Latest;
+        self.save_versions();
+        let reload = self.load_versions();
+
+        let report = if failed.is_empty() {
+            self.show_notice(format!(
+                "Removed {} version{}, freeing {}",
+                removed,
+                if removed == 1 { "" } else { "s" },
+                utils::bytes_to_human_readable_u64(freed, utils::ByteUnits::platform_default())
+            ))
+        } else {
+            self.show_error(format!(
+                "Failed to remove some versions: {}",
+                failed.join(", ")
+            ))
+        };
+        Task::batch([reload, report])
+    }
+
+    /// Adds up the size of the game directory in the background.
+    fn measure_game_dir(&mut self) -> Task<Message> {
+        if self.measuring_game_dir {
+            return Task::none();
+        }
+        self.measuring_game_dir = true;
+        let game_dir = self.launcher_settings.game_dir.clone();
+        Task::perform(
+            async move {
+                let dir = game_dir.clone();
+                let size = tokio::task::spawn_blocking(move || utils::dir_size(&dir))
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|size| size.map_err(|e| e.to_string()));
+                (game_dir, size)
+            },
+            |(game_dir, size)| Message::GameDirMeasured(game_dir, size),
+        )
     }
 
-    async fn fetch_manifest() -> Result<Manifest, String> {
-        reqwest::Client::new()
-            .get("https://muhtasim-rasheed.github.io/mineplace3d/manifest.json")
-            .header("User-Agent", "mineplace3d-launcher")
-            .send()
-            .await
-            .map_err(|e| e.to_string())?
-            .json()
-            .await
-            .map_err(|e| e.to_string())
+    /// Looks for a launcher release newer than this one, unless that is turned off.
+    fn check_launcher_update(&self) -> Task<Message> {
+        if self.launcher_settings.offline || !self.launcher_settings.check_launcher_updates {
+            return Task::none();
+        }
+        let client = match download::build_client(&self.launcher_settings) {
+            Ok(client) => client,
+            Err(e) => return Task::done(Message::LauncherReleaseFetched(Err(e))),
+        };
+        Task::perform(
+            download::fetch_latest_release(
+                client,
+                LAUNCHER_REPO.to_string(),
+                self.launcher_settings.github_token.clone(),
+            ),
+            Message::LauncherReleaseFetched,
+        )
     }
 
-    fn load_versions(&mut self) {
+    /// Reads `versions.json`, returning a task that shows an error if it couldn't be parsed.
+    fn load_versions(&mut self) -> Task<Message> {
         let full_path = self
             .launcher_settings
             .game_dir
             .join("versions")
             .join("versions.json");
-        if let Ok(versions_data) = std::fs::read_to_string(full_path) {
-            let versions: HashSet<Version> =
-                serde_json::from_str(&versions_data).expect("Failed to parse versions data");
-            self.versions = versions;
+        let mut migrated = false;
+        let mut parse_error = None;
+        let records = if let Ok(versions_data) = std::fs::read_to_string(&full_path) {
+            match VersionsFile::parse(&versions_data) {
+                Ok((versions_file, legacy)) => {
+                    migrated = legacy;
+                    versions_file.versions
+                }
+                Err(e) => {
+                    // Keep the broken file around for inspection instead of overwriting it
+                    let backup_path = full_path.with_extension("json.bak");
+                    parse_error = Some(match std::fs::rename(&full_path, &backup_path) {
+                        Ok(()) => format!(
+                            "Failed to parse versions.json: {}. It was moved to {}; use Refresh to find installed versions",
+                            e,
+                            backup_path.display()
+                        ),
+                        Err(backup_error) => format!(
+                            "Failed to parse versions.json: {}. Failed to back it up: {}",
+                            e, backup_error
+                        ),
+                    });
+                    Vec::new()
+                }
+            }
         } else {
-            self.versions = HashSet::new();
+            Vec::new()
+        };
+        // Spellings like `0.3.0` and `0.3.0-release` parse to the same version and collapse here
+        let listed = records.len();
+        self.version_records = records
+            .into_iter()
+            .map(|record| (record.version, record))
+            .collect();
+
+        // Install times used to be kept in a separate file next to the legacy versions.json
+        let install_times_path = self
+            .launcher_settings
+            .game_dir
+            .join("versions")
+            .join("install_times.json");
+        if migrated
+            && let Ok(data) = std::fs::read_to_string(&install_times_path)
+            && let Ok(install_times) =
+                serde_json::from_str::<HashMap<Version, std::time::SystemTime>>(&data)
+        {
+            for (version, installed_at) in install_times {
+                if let Some(record) = self.version_records.get_mut(&version) {
+                    record.installed_at = installed_at
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .ok()
+                        .map(|elapsed| elapsed.as_secs());
+                }
+            }
+        }
+
+        // Binaries deleted behind the launcher's back shouldn't be offered for running
+        let versions_dir = self.launcher_settings.game_dir.join("versions");
+        self.version_records
+            .retain(|v, _| versions_dir.join(version_file_name(*v)).exists());
+        self.versions = self.version_records.keys().copied().collect();
+        if migrated || self.versions.len() != listed {
+            self.save_versions();
+            if migrated {
+                let _ = std::fs::remove_file(install_times_path);
+            }
+        }
+
+        self.load_play_times();
+
+        match parse_error {
+            Some(error) => self.show_error(error),
+            None => Task::none(),
         }
     }
 
-    fn get_latest_downloaded(&self) -> Option<Version> {
-        self.versions.iter().copied().max()
+    fn play_times_path(&self) -> PathBuf {
+        self.launcher_settings
+            .game_dir
+            .join("versions")
+            .join("play_times.json")
     }
 
-    fn get_chosen_downloaded(&self) -> Option<Version> {
-        match self.chosen_playing_version {
-            VersionChoice::Latest => self.get_latest_downloaded(),
-            VersionChoice::Specific(v) => Some(v),
+    /// Reads `play_times.json`, starting over from nothing if it is missing or unreadable.
+    fn load_play_times(&mut self) {
+        self.play_times = std::fs::read_to_string(self.play_times_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+    }
+
+    fn save_play_times(&self) {
+        let play_times_data =
+            serde_json::to_string_pretty(&self.play_times).expect("Failed to serialize play times");
+        if let Err(e) = utils::write_atomic(&self.play_times_path(), play_times_data) {
+            tracing::warn!("Failed to save play times: {}", e);
         }
     }
 
-    fn run_version(&self, version: Version) -> Result<(), String> {
-        if !self.versions.contains(&version) {
-            return Err(format!("Version v{} is not available", version));
+    fn save_versions(&self) {
+        let mut versions: Vec<InstalledVersion> = self
+            .versions
+            .iter()
+            .map(|version| {
+                self.version_records
+                    .get(version)
+                    .cloned()
+                    .unwrap_or_else(|| InstalledVersion {
+                        version: *version,
+                        ..InstalledVersion::default()
+                    })
+            })
+            .collect();
+        versions.sort_by_key(|record| std::cmp::Reverse(record.version));
+        let versions_file = VersionsFile {
+            schema: utils::VERSIONS_SCHEMA,
+            versions,
+        };
+        let versions_data =
+            serde_json::to_string_pretty(&versions_file).expect("Failed to serialize versions");
+        let versions_file_path = self
+            .launcher_settings
+            .game_dir
+            .join("versions")
+            .join("versions.json");
+        if let Err(e) = utils::write_atomic(&versions_file_path, versions_data) {
+            tracing::warn!("Failed to write versions file: {}", e);
         }
+    }
 
-        #[cfg(target_os = "windows")]
-        if !Self::check_sdl2(&self.launcher_settings.game_dir) {
-            return Err(format!(
-                "SDL2 library is not installed. Please put the correct SDL2.dll depending on your architecture into {} to run the game.",
-                self.launcher_settings.game_dir.join("versions").display()
-            ));
+    /// Adds any version binaries found in the versions directory that aren't listed yet.
+    ///
+    /// Only binaries named with the canonical spelling of their version count, so a stray
+    /// `0.3.0-release` next to `0.3.0` never shows up as a second install.
+    fn scan_versions_dir(&mut self) -> std::io::Result<()> {
+        let versions_dir = self.launcher_settings.game_dir.join("versions");
+        let mut found = false;
+        for entry in std::fs::read_dir(&versions_dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            // Unfinished downloads must never show up as installed
+            if Path::new(&file_name)
+                .extension()
+                .is_some_and(|extension| extension == utils::PARTIAL_DOWNLOAD_EXTENSION)
+            {
+                continue;
+            }
+            let Some(version) = file_name.to_str().and_then(utils::version_from_file_name) else {
+                continue;
+            };
+            found |= self.versions.insert(version);
         }
-        #[cfg(target_os = "linux")]
-        if !Self::check_sdl2() {
-            return Err("SDL2 library is not installed. Please install sdl2-compat using your package manager to run the game.".to_string());
+        if found {
+            self.save_versions();
         }
+        Ok(())
+    }
 
-        #[cfg(target_os = "linux")]
-        let exec_path = self
-            .launcher_settings
-            .game_dir
-            .join("versions")
-            .join(version.to_string());
+    /// The manifest's digest for `version`, if it was checked while downloading it.
+    fn verified_manifest_checksum(&self, version: Version) -> Option<String> {
+        self.manifest
+            .as_ref()
+            .filter(|_| self.launcher_settings.verify_checksums)
+            .and_then(|manifest| manifest.versions.get(&version))
+            .and_then(|entry| entry.checksum())
+    }
 
-        #[cfg(target_os = "windows")]
+    /// Records a freshly installed version as installed now, with its verified digest if known.
+    fn record_install(&mut self, version: Version, sha256: Option<String>) {
+        tracing::info!("Installed v{}", version);
+        self.verified_versions.remove(&version);
         let exec_path = self
             .launcher_settings
             .game_dir
             .join("versions")
-            .join(format!("{}.exe", version));
+            .join(version_file_name(version));
+        // Reinstalling a version doesn't change when it was last played
+        let last_played = self
+            .version_records
+            .get(&version)
+            .and_then(|record| record.last_played);
 
-        #[cfg(target_os = "macos")]
-        let exec_path = self
-            .launcher_settings
-            .game_dir
-            .join("versions")
-            .join(format!("{}.app", version));
+        self.versions.insert(version);
+        self.version_records.insert(
+            version,
+            InstalledVersion {
+                version,
+                installed_at: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .ok()
+                    .map(|elapsed| elapsed.as_secs()),
+                size: std::fs::metadata(exec_path)
+                    .ok()
+                    .filter(|metadata| metadata.is_file())
+                    .map(|metadata| metadata.len()),
+                sha256,
+                last_played,
+            },
+        );
+        self.save_versions();
+    }
 
-        #[cfg(not(target_os = "macos"))]
-        std::process::Command::new(&exec_path)
-            .env("MINEPLACE3D_GAME_DIR", &self.launcher_settings.game_dir)
-            .spawn()
-            .map_err(|e| {
-                format!(
-                    "Failed to launch version v{} at {:?}: {}",
-                    version, exec_path, e
-                )
-            })?;
+    /// Starts downloading `version` in the background, reporting progress as
+    /// [`Message::VersionDownloadUpdate`].
+    fn start_download(&mut self, manifest: Manifest, version: Version) -> Task<Message> {
+        let client = match download::build_client(&self.launcher_settings) {
+            Ok(client) => client,
+            Err(e) => return self.show_error(e),
+        };
+        tracing::info!("Downloading v{}", version);
+        self.last_download_attempt = Some(version);
+        self.version_downloading = true;
+        self.version_download_update = DownloadUpdate::default();
 
-        #[cfg(target_os = "macos")]
-        std::process::Command::new("open")
-            .arg(&exec_path)
-            .env("MINEPLACE3D_GAME_DIR", &self.launcher_settings.game_dir)
-            .spawn()
-            .map_err(|e| {
-                format!(
-                    "Failed to launch version v{} at {:?}: {}",
-                    version, exec_path, e
-                )
-            })?;
+        let game_dir = self.launcher_settings.game_dir.clone();
+        let options = DownloadOptions::from(&self.launcher_settings);
 
-        Ok(())
+        let (task, handle) = Task::run(
+            download::fetch(client, manifest, game_dir, version, options),
+            |event| match event {
+                DownloadEvent::Update(update) => Message::VersionDownloadUpdate(update),
+                DownloadEvent::Done(Ok(v)) => Message::VersionDownloaded(v),
+                DownloadEvent::Done(Err(e)) => Message::VersionDownloadFailed(e),
+            },
+        )
+        .abortable();
+        self.version_download_task = Some((version, handle));
+
+        task
     }
 
-    async fn download_version(
-        manifest: Manifest,
-        game_dir: PathBuf,
-        version: Version,
-        mut progress_tx: Sender<Message>,
-    ) -> Result<Version, String> {
-        async fn download_to_file(
-            content_length: Option<u64>,
-            mut stream: impl iced::futures::Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin,
-            path: std::path::PathBuf,
-            progress_tx: &mut Sender<Message>,
-        ) -> Result<(), String> {
-            let mut file = tokio::fs::File::create(&path)
-                .await
-                .map_err(|e| e.to_string())?;
-
-            let mut downloaded = 0u64;
-            let mut last_progress = 0.0;
-
-            let mut last_tick = std::time::Instant::now();
-            let mut downloaded_since_last = 0u64;
-
-            let stall_timeout = std::time::Duration::from_secs(10);
-            let mut last_chunk_at = std::time::Instant::now();
-
-            loop {
-                tokio::select! {
-                    chunk = stream.next() => {
-                        match chunk {
-                            Some(Ok(bytes)) => {
-                                last_chunk_at = std::time::Instant::now();
-
-                                file.write_all(&bytes)
-                                    .await
-                                    .map_err(|e| e.to_string())?;
-
-                                let len = bytes.len() as u64;
-                                downloaded += len;
-                                downloaded_since_last += len;
-
-                                let elapsed = last_tick.elapsed();
-
-                                if elapsed >= std::time::Duration::from_millis(250) {
-                                    if let Some(total) = content_length {
-                                        let progress = downloaded as f32 / total as f32;
-                                        last_progress = progress;
-
-                                        let speed = downloaded_since_last as f32 / elapsed.as_secs_f32();
-
-                                        let _ = progress_tx.try_send(Message::VersionDownloadUpdate(
-                                            DownloadUpdate::new(progress, speed),
-                                        ));
-                                    }
-
-                                    downloaded_since_last = 0;
-                                    last_tick = std::time::Instant::now();
-                                }
-                            }
-                            Some(Err(e)) => {
-                                let _ = progress_tx.try_send(Message::VersionDownloadUpdate(
-                                    DownloadUpdate::Failed {
-                                        last_progress: Some(last_progress),
-                                    }
-                                ));
-                                return Err(e.to_string());
-                            }
-                            None => break,
-                        }
-                    }
+    /// Ends the running download with `error`, keeping its progress bar on screen until it is
+    /// dismissed.
+    fn fail_download(&mut self, error: String) -> Task<Message> {
+        let show_error = self.show_error(error);
+        self.version_downloading = false;
+        if let DownloadUpdate::Progress { progress, .. } = self.version_download_update {
+            self.version_download_update = DownloadUpdate::Failed {
+                last_progress: Some(progress),
+            };
+        } else {
+            self.version_download_update = DownloadUpdate::Failed {
+                last_progress: None,
+            };
+        }
+        show_error
+    }
 
-                    _ = tokio::time::sleep(stall_timeout) => {
-                        if last_chunk_at.elapsed() >= stall_timeout {
-                            let _ = progress_tx.try_send(Message::VersionDownloadUpdate(
-                                DownloadUpdate::Failed {
-                                    last_progress: Some(last_progress),
-                                }
-                            ));
-                            return Err("Download stalled".to_string());
-                        }
-                    }
-                }
-            }
+    /// The newest version published in the manifest, if it has been fetched.
+    fn latest_remote_version(&self) -> Option<Version> {
+        self.manifest.as_ref()?.get_latest_version()
+    }
 
-            Ok(())
-        }
+    /// The newest published version, if it is newer than everything installed in semver
+    /// order, so `0.4.0-alpha.1` counts as an update to `0.3.0`.
+    fn available_update(&self) -> Option<Version> {
+        let latest_remote = self
+            .latest_remote_version()
+            .filter(|version| self.channel_enabled(*version))?;
+        let latest_installed = self.versions.iter().copied().max_by(Version::cmp_semver)?;
+        latest_remote
+            .cmp_semver(&latest_installed)
+            .is_gt()
+            .then_some(latest_remote)
+    }
 
-        let client = reqwest::Client::new();
+    /// Applies the values from the settings inputs, using `new_game_dir` as the game directory,
+    /// and saves them. Nothing is applied if the game directory or the settings file can't be
+    /// written.
+    fn apply_settings(&mut self, new_game_dir: PathBuf) -> Task<Message> {
+        if let Err(e) = Self::setup_folder_structure(&new_game_dir) {
+            return self.show_error(format!("Can't write to {}: {}", new_game_dir.display(), e));
+        }
 
-        let entry = manifest
-            .versions
-            .get(&version)
-            .ok_or_else(|| format!("Version v{} not found in manifest", version))?;
+        let releases_repo = self.input_releases_repo_content.trim().to_string();
+        let previous_settings = self.launcher_settings.clone();
 
-        let platform = utils::platform_key();
+        self.launcher_settings.set_game_dir(new_game_dir);
+        self.launcher_settings.launch_args = utils::split_args(&self.input_launch_args_content);
+        let launch_wrapper = self.input_launch_wrapper_content.trim();
+        self.launcher_settings.launch_wrapper =
+            (!launch_wrapper.is_empty()).then(|| launch_wrapper.to_string());
+        let github_token = self.input_github_token_content.trim();
+        self.launcher_settings.github_token =
+            (!github_token.is_empty()).then(|| github_token.to_string());
+        let http_proxy = self.input_http_proxy_content.trim();
+        self.launcher_settings.http_proxy =
+            (!http_proxy.is_empty()).then(|| http_proxy.to_string());
+        if let Ok(secs) = self.input_network_timeout_content.trim().parse() {
+            self.launcher_settings.network_timeout_secs = secs;
+        }
+        self.launcher_settings.max_download_bytes_per_sec = self
+            .input_max_download_speed_content
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .and_then(|kib| kib.checked_mul(1024));
+        let download_staging_dir = self.input_download_staging_dir_content.trim();
+        self.launcher_settings.download_staging_dir =
+            (!download_staging_dir.is_empty()).then(|| PathBuf::from(download_staging_dir));
+        let repo_changed = releases_repo != self.launcher_settings.releases_repo;
+        self.launcher_settings.releases_repo = releases_repo;
 
-        let file = entry
-            .files
-            .get(&platform)
-            .ok_or_else(|| format!("No binary for platform: {}", platform))?;
+        if let Err(e) = self.save_launcher_settings() {
+            self.launcher_settings = previous_settings;
+            return self.show_error(format!(
+                "Can't write to {}: {}",
+                LauncherSettings::file_path().display(),
+                e
+            ));
+        }
 
-        let download_url = format!(
-            "{}v{}/{}",
-            manifest.base,
-            entry.real_name.clone().unwrap_or(version.to_string()),
-            file
+        self.view = View::Play;
+        self.input_game_dir_content = self
+            .launcher_settings
+            .game_dir
+            .to_string_lossy()
+            .to_string();
+        self.input_launch_args_content = utils::join_args(&self.launcher_settings.launch_args);
+        tracing::info!(
+            "Settings saved successfully. New game directory: {:?}",
+            self.launcher_settings.game_dir
         );
+        self.last_error = None;
+        let show_versions_error = self.load_versions();
+
+        if repo_changed {
+            self.manifest = None;
+            self.release_notes.clear();
+            Task::batch([show_versions_error, self.refetch_manifest()])
+        } else {
+            show_versions_error
+        }
+    }
+
+    /// Fills the settings inputs with the values of the current settings.
+    fn reset_settings_inputs(&mut self) {
+        let settings = &self.launcher_settings;
+        self.input_game_dir_content = settings.game_dir.to_string_lossy().to_string();
+        self.input_launch_args_content = utils::join_args(&settings.launch_args);
+        self.input_launch_wrapper_content = settings.launch_wrapper.clone().unwrap_or_default();
+        self.input_github_token_content = settings.github_token.clone().unwrap_or_default();
+        self.input_http_proxy_content = settings.http_proxy.clone().unwrap_or_default();
+        self.input_network_timeout_content = settings.network_timeout_secs.to_string();
+        self.input_max_download_speed_content = settings
+            .max_download_bytes_per_sec
+            .map(|limit| (limit / 1024).to_string())
+            .unwrap_or_default();
+        self.input_download_staging_dir_content = settings
+            .download_staging_dir
+            .as_ref()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.input_releases_repo_content = settings.releases_repo.clone();
+    }
+
+    /// Makes the profile called `name` the active one and loads the versions in its game
+    /// directory.
+    fn switch_profile(&mut self, name: &str) -> Task<Message> {
+        if name == self.launcher_settings.active_profile {
+            return Task::none();
+        }
+        if self.version_downloading || self.game_dir_move_progress.is_some() {
+            return self.show_error("Wait for the current download or move to finish");
+        }
+        let Some(profile) = self
+            .launcher_settings
+            .profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .cloned()
+        else {
+            return Task::none();
+        };
+        if let Err(e) = Self::setup_folder_structure(&profile.game_dir) {
+            return self.show_error(format!(
+                "Can't write to {}: {}",
+                profile.game_dir.display(),
+                e
+            ));
+        }
 
-        let download_response = client
-            .get(&download_url)
-            .header("User-Agent", "mineplace3d-launcher")
-            .send()
+        self.launcher_settings.active_profile = profile.name;
+        self.launcher_settings.game_dir = profile.game_dir;
+        self.chosen_playing_version = VersionChoice::Latest;
+        self.reset_settings_inputs();
+        let show_save_error = match self.save_launcher_settings() {
+            Ok(()) => Task::none(),
+            Err(e) => self.show_error(format!("Failed to save launcher settings: {}", e)),
+        };
+        Task::batch([show_save_error, self.load_versions()])
+    }
+
+    /// Replaces the settings with the ones in the exported settings file at `path`.
+    fn import_settings(&mut self, path: &Path) -> Result<Task<Message>, String> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let imported: LauncherSettings = serde_json::from_str(&data)
+            .map_err(|e| format!("{} is not a settings file: {}", path.display(), e))?;
+        let reload = self.replace_settings(imported)?;
+        Ok(Task::batch([
+            reload,
+            self.show_notice(format!("Imported settings from {}", path.display())),
+        ]))
+    }
+
+    /// Saves `settings` in place of the current ones and reloads everything that depends on
+    /// them. Game directories are only ever created here, never removed.
+    fn replace_settings(&mut self, settings: LauncherSettings) -> Result<Task<Message>, String> {
+        Self::setup_folder_structure(&settings.game_dir)
+            .map_err(|e| format!("Can't write to {}: {}", settings.game_dir.display(), e))?;
+
+        let previous_settings = std::mem::replace(&mut self.launcher_settings, settings);
+        if let Err(e) = self.launcher_settings.save() {
+            self.launcher_settings = previous_settings;
+            return Err(format!(
+                "Can't write to {}: {}",
+                LauncherSettings::file_path().display(),
+                e
+            ));
+        }
+
+        self.reset_settings_inputs();
+        self.chosen_playing_version = VersionChoice::Latest;
+        self.manifest = None;
+        self.network_unavailable = false;
+        self.release_notes.clear();
+        let show_versions_error = self.load_versions();
+        Ok(Task::batch([show_versions_error, self.refetch_manifest()]))
+    }
+
+    /// Checks that `path` can be used as the game directory before any settings are changed.
+    ///
+    /// The path has to be absolute and writable, or creatable in a writable parent, and must not
+    /// lie inside the current game directory, which moving it would copy into itself.
+    fn validate_game_dir(&self, path: &Path) -> Result<(), String> {
+        if !path.is_absolute() {
+            return Err(format!(
+                "Game directory must be an absolute path, got {}",
+                path.display()
+            ));
+        }
+        if path.is_file() {
+            return Err(format!("{} is a file, not a directory", path.display()));
+        }
+
+        let current = &self.launcher_settings.game_dir;
+        let current = std::fs::canonicalize(current).unwrap_or_else(|_| current.clone());
+        // The new directory may not exist yet, so resolve its closest existing parent instead
+        let existing = path
+            .ancestors()
+            .find(|ancestor| ancestor.exists())
+            .ok_or_else(|| format!("{} has no existing parent directory", path.display()))?;
+        let resolved = match (std::fs::canonicalize(existing), path.strip_prefix(existing)) {
+            (Ok(canonical), Ok(rest)) => canonical.join(rest),
+            _ => path.to_path_buf(),
+        };
+        if resolved != current && resolved.starts_with(&current) {
+            return Err(format!(
+                "Game directory can't be inside the current one at {}",
+                current.display()
+            ));
+        }
+
+        if !existing.is_dir() {
+            return Err(format!("{} is a file, not a directory", existing.display()));
+        }
+        utils::check_writable(existing)
+            .map_err(|e| format!("Can't write to {}: {}", existing.display(), e))
+    }
+
+    /// Copies the game directory to `new_game_dir` on a blocking thread, reporting progress as
+    /// [`Message::GameDirMoveProgress`] and finishing with [`Message::GameDirMoved`].
+    fn move_game_dir(old_game_dir: PathBuf, new_game_dir: PathBuf) -> Task<Message> {
+        Task::stream(iced::stream::channel(100, async move |mut output| {
+            let mut progress_tx = output.clone();
+            let target = new_game_dir.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                copy_dir(&old_game_dir, &target, &mut |copied, total| {
+                    let progress = if total == 0 {
+                        1.0
+                    } else {
+                        copied as f32 / total as f32
+                    };
+                    let _ = progress_tx.try_send(Message::GameDirMoveProgress(progress));
+                })
+                .map_err(|e| e.to_string())
+            })
             .await
-            .map_err(|e| format!("Failed to download asset: {}", e))?;
+            .map_err(|e| e.to_string())
+            .and_then(|result| result);
+
+            let _ = output
+                .send(Message::GameDirMoved(result.map(|_| new_game_dir)))
+                .await;
+        }))
+    }
 
-        if !download_response.status().is_success() {
-            return Err(format!("Failed to download version v{}", version));
+    /// Starts `version` unless it is missing or already running, keeping track of the process.
+    fn launch_version(&mut self, version: Option<Version>) -> Result<(), String> {
+        let Some(version) = version else {
+            return Err("You have no versions downloaded".to_string());
+        };
+        if !self.versions.contains(&version) {
+            return Err("You have not installed this version".to_string());
+        }
+        if self.running_games.contains_key(&version) {
+            return Err(format!("Version v{} is already running", version));
         }
+        if self.clean_run {
+            return self.launch_clean(version);
+        }
+        let child = self
+            .run_version(version, &self.launcher_settings.game_dir)
+            .map_err(|e| format!("Error running version: {}", e))?;
+        self.running_games
+            .insert(version, (child, std::time::Instant::now()));
+        self.record_launch(version);
+        Ok(())
+    }
 
-        let exec_path = game_dir
-            .join("versions")
-            .join(if cfg!(target_os = "windows") {
-                format!("{}.exe", version)
-            } else if cfg!(target_os = "macos") {
-                format!("{}.app", version)
-            } else {
-                version.to_string().clone()
-            });
+    /// Starts `version` against a new, empty game directory in the temp folder. Nothing about
+    /// the run is recorded, so the real game directory and play history stay as they were.
+    fn launch_clean(&mut self, version: Version) -> Result<(), String> {
+        let game_dir = std::env::temp_dir().join(format!(
+            "mineplace3d-clean-{}-{}",
+            version,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        Self::setup_folder_structure(&game_dir).map_err(|e| {
+            format!(
+                "Failed to create clean game directory {}: {}",
+                game_dir.display(),
+                e
+            )
+        })?;
+        let child = match self.run_version(version, &game_dir) {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&game_dir);
+                return Err(format!("Error running version: {}", e));
+            }
+        };
+        tracing::info!(
+            "Launched v{} in clean game directory {:?}",
+            version,
+            game_dir
+        );
+        self.running_games
+            .insert(version, (child, std::time::Instant::now()));
+        self.clean_runs
+            .insert(version, (game_dir, self.keep_clean_game_dir));
+        Ok(())
+    }
+
+    /// Records `version` as last played now.
+    fn record_launch(&mut self, version: Version) {
+        tracing::info!("Launched v{}", version);
+        let Some(record) = self.version_records.get_mut(&version) else {
+            return;
+        };
+        record.last_played = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|elapsed| elapsed.as_secs());
+        self.save_versions();
+    }
 
-        let total_size = download_response.content_length();
-        let stream = download_response.bytes_stream();
+    /// Adds `elapsed` to the time played in `version` and saves it.
+    fn record_play_time(&mut self, version: Version, elapsed: std::time::Duration) {
+        *self.play_times.entry(version).or_default() += elapsed;
+        self.save_play_times();
+    }
 
-        download_to_file(total_size, stream, exec_path.clone(), &mut progress_tx).await?;
+    /// Shows `error` under the tab bar until [`ERROR_DISPLAY_TIME`] has passed.
+    fn show_error(&mut self, error: impl Into<String>) -> Task<Message> {
+        let error = error.into();
+        tracing::error!("{}", error);
+        let shown_at = std::time::Instant::now();
+        self.latest_error = Some(error.clone());
+        self.last_error = Some((error, shown_at));
+        Task::perform(tokio::time::sleep(ERROR_DISPLAY_TIME), move |_| {
+            Message::ErrorExpired(shown_at)
+        })
+    }
 
-        let _ = progress_tx.try_send(Message::VersionDownloadUpdate(DownloadUpdate::Finished));
+    /// Shows `notice` under the tab bar until [`NOTICE_DISPLAY_TIME`] has passed.
+    fn show_notice(&mut self, notice: impl Into<String>) -> Task<Message> {
+        let shown_at = std::time::Instant::now();
+        self.last_notice = Some((notice.into(), shown_at));
+        Task::perform(tokio::time::sleep(NOTICE_DISPLAY_TIME), move |_| {
+            Message::NoticeExpired(shown_at)
+        })
+    }
 
-        // Are we on windows? If so, install SDL2.dll if not present
-        #[cfg(target_os = "windows")]
-        {
-            let sdl2_path = game_dir.join("versions").join("SDL2.dll");
-            if !sdl2_path.exists() {
-                #[cfg(target_arch = "x86_64")]
-                let sdl2_url = "https://www.libsdl.org/release/SDL2-2.32.10-win32-x64.zip";
-
-                // SDL doesn't provide official arm64 builds, so we use a community build
-                #[cfg(target_arch = "aarch64")]
-                let sdl2_url = "https://www.github.com/mmozeiko/build-sdl2/releases/download/2025-12-28/SDL2-arm64-2025-12-28.zip";
-
-                let sdl2_response = client
-                    .get(sdl2_url)
-                    .header("User-Agent", "mineplace3d-launcher")
-                    .send()
-                    .await
-                    .map_err(|e| format!("Failed to download SDL2.dll: {}", e))?;
+    /// Persists the launcher settings together with the current session state.
+    fn save_launcher_settings(&mut self) -> std::io::Result<()> {
+        self.launcher_settings.last_selected_version = match self.chosen_playing_version {
+            VersionChoice::Latest => None,
+            VersionChoice::Specific(v) => Some(v),
+        };
+        self.launcher_settings.save()
+    }
 
-                if !sdl2_response.status().is_success() {
-                    return Err("Failed to download SDL2.dll".to_string());
-                }
+    /// Saves the window size and the picked version on top of the saved settings, so edits on
+    /// the settings tab that were never saved are dropped rather than kept on close.
+    fn save_window_state(&self) -> Result<(), String> {
+        // Without a settings file the launcher is running on the defaults
+        let mut saved = LauncherSettings::load()?.unwrap_or_default();
+        saved.window_size = self.launcher_settings.window_size;
+        saved.last_selected_version = match self.chosen_playing_version {
+            VersionChoice::Latest => None,
+            VersionChoice::Specific(v) => Some(v),
+        };
+        saved
+            .save()
+            .map_err(|e| format!("Failed to write launcher configuration file: {}", e))
+    }
+
+    /// The most recently modified log file in `game_dir/logs/`.
+    fn latest_log(&self) -> Option<PathBuf> {
+        std::fs::read_dir(self.launcher_settings.game_dir.join("logs"))
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .max_by_key(|(modified, _)| *modified)
+            .map(|(_, path)| path)
+    }
 
-                let temp_zip_path = game_dir.join("versions").join("sdl2_temp.zip");
+    /// The newest installed version in one of the enabled release channels.
+    fn get_latest_downloaded(&self) -> Option<Version> {
+        self.versions
+            .iter()
+            .copied()
+            .filter(|version| self.channel_enabled(*version))
+            .max()
+    }
 
-                let _ = progress_tx.try_send(Message::VersionDownloadUpdate(DownloadUpdate::new(
-                    0.0, 0.0,
-                )));
+    fn get_chosen_downloaded(&self) -> Option<Version> {
+        match self.chosen_playing_version {
+            VersionChoice::Latest => self.get_latest_downloaded(),
+            VersionChoice::Specific(v) => Some(v),
+        }
+    }
 
-                let total_size = sdl2_response.content_length();
-                let stream = sdl2_response.bytes_stream();
+    /// Whether one of the modal dialogs stacked in `view` is showing.
+    fn dialog_open(&self) -> bool {
+        self.pending_game_dir_move.is_some()
+            || self.pending_local_install.is_some()
+            || self.corrupt_version.is_some()
+            || self.confirm_remove_all_versions
+            || self.confirm_reset_settings
+            || self.changelog_version.is_some()
+    }
 
-                download_to_file(total_size, stream, temp_zip_path.clone(), &mut progress_tx)
-                    .await
-                    .map_err(|e| format!("Failed to download SDL2.dll: {}", e))?;
+    /// Starts `version` with `game_dir` as the directory it keeps its data and logs in.
+    fn run_version(
+        &self,
+        version: Version,
+        game_dir: &Path,
+    ) -> Result<std::process::Child, String> {
+        if !self.versions.contains(&version) {
+            return Err(format!("Version v{} is not available", version));
+        }
+
+        #[cfg(target_os = "windows")]
+        if !Self::check_sdl2(&self.launcher_settings.game_dir) {
+            return Err(format!(
+                "SDL2 library is not installed. Please put the correct SDL2.dll depending on your architecture into {} to run the game.",
+                self.launcher_settings.game_dir.join("versions").display()
+            ));
+        }
+        // A bundled SDL2 next to the binaries takes the place of the system's
+        #[cfg(target_os = "linux")]
+        let versions_dir = self.launcher_settings.game_dir.join("versions");
+        #[cfg(target_os = "linux")]
+        let bundled_sdl2 = self.launcher_settings.bundle_sdl2
+            && versions_dir.join(download::SDL2_FILE_NAME).exists();
+        #[cfg(target_os = "linux")]
+        if !bundled_sdl2 && Self::find_sdl2().is_none() {
+            let searched: Vec<String> = utils::library_search_dirs()
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect();
+            return Err(format!(
+                "SDL2 library is not installed: ldconfig doesn't list it and it isn't in {}. Please install sdl2-compat using your package manager, or turn on the bundled SDL2 in the settings, to run the game.",
+                searched.join(", ")
+            ));
+        }
 
-                let _ =
-                    progress_tx.try_send(Message::VersionDownloadUpdate(DownloadUpdate::Finished));
+        #[cfg(target_os = "linux")]
+        let exec_path = self
+            .launcher_settings
+            .game_dir
+            .join("versions")
+            .join(version.to_string());
+
+        #[cfg(target_os = "windows")]
+        let exec_path = self
+            .launcher_settings
+            .game_dir
+            .join("versions")
+            .join(format!("{}.exe", version));
+
+        #[cfg(target_os = "macos")]
+        let exec_path = self
+            .launcher_settings
+            .game_dir
+            .join("versions")
+            .join(format!("{}.app", version));
 
-                let mut zip = zip::ZipArchive::new(
-                    std::fs::File::open(&temp_zip_path)
-                        .map_err(|e| format!("Failed to open SDL2.dll zip file: {}", e))?,
+        // Game output goes to a fresh log file per launch so crashes can be reported
+        let logs_dir = game_dir.join("logs");
+        std::fs::create_dir_all(&logs_dir)
+            .map_err(|e| format!("Failed to create logs directory: {}", e))?;
+        let log_path = logs_dir.join(format!(
+            "{}-{}.log",
+            version,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+
+        #[cfg(not(target_os = "macos"))]
+        let child = {
+            let log_file = std::fs::File::create(&log_path)
+                .map_err(|e| format!("Failed to create log file {:?}: {}", log_path, e))?;
+
+            #[cfg(target_os = "linux")]
+            let library_path = if bundled_sdl2 {
+                let library_path = std::env::join_paths(
+                    std::iter::once(versions_dir.clone()).chain(
+                        std::env::var_os("LD_LIBRARY_PATH")
+                            .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+                            .unwrap_or_default(),
+                    ),
                 )
-                .map_err(|e| format!("Failed to read SDL2.dll zip archive: {}", e))?;
+                .map_err(|e| format!("Failed to build LD_LIBRARY_PATH: {}", e))?;
+                Some(library_path)
+            } else {
+                None
+            };
 
-                let mut sdl2_file = zip
-                    .by_name("SDL2.dll")
-                    .map_err(|e| format!("Failed to find SDL2.dll in zip archive: {}", e))?;
+            let spawn = || {
+                let mut command = utils::game_command(
+                    &exec_path,
+                    self.launcher_settings.launch_wrapper.as_deref(),
+                );
+                #[cfg(target_os = "linux")]
+                if let Some(library_path) = &library_path {
+                    command.env("LD_LIBRARY_PATH", library_path);
+                }
+                command
+                    .args(&self.launcher_settings.launch_args)
+                    .env("MINEPLACE3D_GAME_DIR", game_dir)
+                    .stdout(std::process::Stdio::from(log_file.try_clone()?))
+                    .stderr(std::process::Stdio::from(log_file.try_clone()?))
+                    .spawn()
+            };
+            let spawned = match spawn() {
+                // Some filesystems and archive tools drop the executable bit, so restore it and
+                // try once more
+                #[cfg(unix)]
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::PermissionDenied
+                        && utils::set_executable(&exec_path).is_ok() =>
+                {
+                    spawn()
+                }
+                spawned => spawned,
+            };
+            spawned.map_err(|e| Self::launch_error(version, &exec_path, &e))?
+        };
+
+        // `open` hands the app to launchd, so it has to be told where the app's output goes.
+        // `-W` keeps `open` alive until the app quits so the launcher can tell it is running.
+        #[cfg(target_os = "macos")]
+        let child = std::process::Command::new("open")
+            .arg("-W")
+            .arg(&exec_path)
+            .arg("--stdout")
+            .arg(&log_path)
+            .arg("--stderr")
+            .arg(&log_path)
+            .arg("--args")
+            .args(&self.launcher_settings.launch_args)
+            .env("MINEPLACE3D_GAME_DIR", game_dir)
+            .spawn()
+            .map_err(|e| Self::launch_error(version, &exec_path, &e))?;
 
-                let mut sdl2_out = std::fs::File::create(&sdl2_path)
-                    .map_err(|e| format!("Failed to create SDL2.dll file: {}", e))?;
-                std::io::copy(&mut sdl2_file, &mut sdl2_out)
-                    .map_err(|e| format!("Failed to write SDL2.dll file: {}", e))?;
+        Ok(child)
+    }
 
-                std::fs::remove_file(&temp_zip_path)
-                    .map_err(|e| format!("Failed to remove temporary SDL2.dll zip file: {}", e))?;
+    /// Explains why `version` at `exec_path` couldn't be started, with a way out for the
+    /// platform's usual reasons for refusing to run a binary.
+    fn launch_error(version: Version, exec_path: &Path, error: &std::io::Error) -> String {
+        if error.kind() != std::io::ErrorKind::PermissionDenied {
+            return format!(
+                "Failed to launch version v{} at {:?}: {}",
+                version, exec_path, error
+            );
+        }
+        if cfg!(target_os = "macos") {
+            format!(
+                "macOS blocked v{}. If it is quarantined, run `xattr -dr com.apple.quarantine \"{}\"` in a terminal and try again",
+                version,
+                exec_path.display()
+            )
+        } else if cfg!(target_os = "windows") {
+            format!(
+                "Windows refused to start v{}; check whether antivirus software blocked {}",
+                version,
+                exec_path.display()
+            )
+        } else {
+            format!(
+                "v{} can't be executed even after restoring its executable bit; the filesystem holding {} may be mounted with noexec",
+                version,
+                exec_path.display()
+            )
+        }
+    }
 
-                let _ =
-                    progress_tx.try_send(Message::VersionDownloadUpdate(DownloadUpdate::Finished));
+    /// Copies a locally built binary into the versions directory as `version`.
+    async fn install_local_binary(
+        source: PathBuf,
+        game_dir: PathBuf,
+        version: Version,
+    ) -> Result<Version, String> {
+        let exec_path = game_dir.join("versions").join(version_file_name(version));
+        tokio::task::spawn_blocking(move || {
+            // macOS app bundles are directories, everything else is a single file
+            if exec_path.is_dir() {
+                std::fs::remove_dir_all(&exec_path)
+                    .map_err(|e| format!("Failed to remove the old v{}: {}", version, e))?;
             }
-        }
+            if source.is_dir() {
+                copy_dir(&source, &exec_path, &mut |_, _| {})
+            } else {
+                std::fs::copy(&source, &exec_path).map(|_| ())
+            }
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
 
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = std::fs::metadata(&exec_path)
-                .map_err(|e| format!("Failed to get metadata for {}: {}", exec_path.display(), e))?
-                .permissions();
-            perms.set_mode(0o755);
-            std::fs::set_permissions(&exec_path, perms).map_err(|e| {
+            utils::set_executable(&exec_path).map_err(|e| {
                 format!(
                     "Failed to set permissions for {}: {}",
                     exec_path.display(),
                     e
                 )
             })?;
-        }
-
-        Ok(version)
+            Ok(version)
+        })
+        .await
+        .map_err(|e| e.to_string())?
     }
 
+    /// Where the SDL2 library is installed, asking the linker cache first and then looking
+    /// through the library directories for installs it doesn't know about.
     #[cfg(target_os = "linux")]
-    fn check_sdl2() -> bool {
-        use std::process::Command;
-
-        let output = Command::new("ldconfig")
+    fn find_sdl2() -> Option<PathBuf> {
+        // `ldconfig` is missing from PATH on some distros, which only skips this step
+        let from_cache = std::process::Command::new("ldconfig")
             .arg("-p")
             .output()
-            .expect("Failed to execute ldconfig");
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                // Lines look like `libSDL2-2.0.so.0 (libc6,x86-64) => /usr/lib/libSDL2-2.0.so.0`
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter(|line| {
+                        line.split_whitespace()
+                            .next()
+                            .is_some_and(|name| utils::is_shared_library(name, "libSDL2"))
+                    })
+                    .find_map(|line| line.split_once("=>"))
+                    .map(|(_, path)| PathBuf::from(path.trim()))
+            });
+        from_cache.or_else(|| utils::find_library(&utils::library_search_dirs(), "libSDL2"))
+    }
 
-        if !output.status.success() {
-            return false;
+    /// Describes which SDL2 library the game would load, for diagnostics.
+    fn sdl2_status(&self) -> String {
+        #[cfg(target_os = "linux")]
+        {
+            let bundled = self
+                .launcher_settings
+                .game_dir
+                .join("versions")
+                .join(download::SDL2_FILE_NAME);
+            if self.launcher_settings.bundle_sdl2 && bundled.exists() {
+                format!("bundled at `{}`", bundled.display())
+            } else {
+                match Self::find_sdl2() {
+                    Some(path) => format!("system library at `{}`", path.display()),
+                    None => "not found".to_string(),
+                }
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            if Self::check_sdl2(&self.launcher_settings.game_dir) {
+                format!("{} installed", download::SDL2_FILE_NAME)
+            } else {
+                format!("{} missing", download::SDL2_FILE_NAME)
+            }
+        }
+        // The launcher doesn't look for or install SDL2 anywhere else
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            "not applicable on this platform".to_string()
         }
+    }
+
+    /// A Markdown summary of the launcher's environment to paste into bug reports. The platform
+    /// and architecture are the ones release assets are matched against.
+    fn diagnostics(&self) -> String {
+        let (os, arch) = (utils::platform_os(), utils::platform_arch());
+        let mut versions: Vec<&Version> = self.versions.iter().collect();
+        versions.sort_by(|a, b| b.cmp(a));
+        let versions = if versions.is_empty() {
+            "none".to_string()
+        } else {
+            versions
+                .iter()
+                .map(|v| format!("v{}", v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        stdout.contains("libSDL2")
+        format!(
+            "## Mineplace3D Launcher diagnostics\n\n\
+             - **Launcher version:** {}\n\
+             - **Platform:** {} ({})\n\
+             - **Asset keys:** {}_{}, {}\n\
+             - **Game directory:** `{}`\n\
+             - **SDL2:** {}\n\
+             - **Installed versions:** {}\n\
+             - **Last error:** {}\n",
+            env!("CARGO_PKG_VERSION"),
+            os,
+            arch,
+            os,
+            arch,
+            os,
+            self.launcher_settings.game_dir.display(),
+            self.sdl2_status(),
+            versions,
+            self.latest_error.as_deref().unwrap_or("none"),
+        )
     }
 
     #[cfg(target_os = "windows")]
@@ -537,126 +2039,731 @@ impl Launcher {
         true
     }
 
-    /// Subscription to handle download progress updates.
-    ///
-    /// This subscription sets up a channel to receive progress updates from the
-    /// asynchronous download task and simply forwards them as messages to the main application.
-    fn subscription(&self) -> Subscription<Message> {
-        Subscription::run(|| {
-            iced::stream::channel(100, async |mut sender| {
-                let (tx, mut rx) = iced::futures::channel::mpsc::channel(100);
-
-                // Notify the main application that the progress update channel is ready and give
-                // it a way to send progress updates.
-                sender
-                    .send(Message::VersionDownloadUpdateReady(tx))
-                    .await
-                    .unwrap();
+    /// Watches `versions_dir` for version binaries being added or removed, sending
+    /// [`Message::VersionsChanged`] once a burst of changes has settled.
+    // `Subscription::run_with` hands over a reference to the exact data it was given
+    #[allow(clippy::ptr_arg)]
+    fn watch_versions_dir(
+        versions_dir: &PathBuf,
+    ) -> impl iced::futures::Stream<Item = Message> + use<> {
+        let versions_dir = versions_dir.clone();
+        iced::stream::channel(10, async move |mut output| {
+            use notify::Watcher;
 
-                // Forward progress updates from the download task to the main application
-                loop {
-                    if let Some(Message::VersionDownloadUpdate(update)) = rx.next().await {
-                        sender
-                            .send(Message::VersionDownloadUpdate(update))
-                            .await
-                            .unwrap();
+            let (mut tx, mut rx) = iced::futures::channel::mpsc::channel(100);
+            let watcher =
+                notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                    // The launcher's own JSON files live here too, so only binaries count
+                    if let Ok(event) = event
+                        && !event.kind.is_access()
+                        && event.paths.iter().any(|path| {
+                            path.file_name()
+                                .and_then(|name| name.to_str())
+                                .and_then(utils::version_from_file_name)
+                                .is_some()
+                        })
+                    {
+                        let _ = tx.try_send(());
                     }
+                });
+            let mut watcher = match watcher {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    tracing::warn!("Failed to watch {:?}: {}", versions_dir, e);
+                    return;
                 }
-            })
+            };
+            if let Err(e) = watcher.watch(&versions_dir, notify::RecursiveMode::NonRecursive) {
+                tracing::warn!("Failed to watch {:?}: {}", versions_dir, e);
+                return;
+            }
+
+            while rx.next().await.is_some() {
+                while let Ok(Some(())) =
+                    tokio::time::timeout(VERSIONS_WATCH_DEBOUNCE, rx.next()).await
+                {}
+                if output.send(Message::VersionsChanged).await.is_err() {
+                    break;
+                }
+            }
         })
     }
 
+    /// Subscription to poll running games and opened file managers and listen for window and
+    /// keyboard events.
+    fn subscription(&self) -> Subscription<Message> {
+        let running_games = if self.running_games.is_empty() && self.opened_processes.is_empty() {
+            Subscription::none()
+        } else {
+            iced::time::every(std::time::Duration::from_secs(1)).map(|_| Message::PollRunningGames)
+        };
+
+        // Keyed by the directory, so the watcher is replaced when the game directory changes
+        let versions_watcher = if self.view == View::FirstRun {
+            Subscription::none()
+        } else {
+            Subscription::run_with(
+                self.launcher_settings.game_dir.join("versions"),
+                Self::watch_versions_dir,
+            )
+        };
+
+        Subscription::batch([
+            running_games,
+            versions_watcher,
+            iced::window::resize_events().map(|(_, size)| Message::WindowResized(size)),
+            iced::window::close_requests().map(Message::WindowCloseRequested),
+            iced::keyboard::listen().filter_map(|event| match event {
+                iced::keyboard::Event::KeyPressed { key, modifiers, .. } => {
+                    Some(Message::KeyPressed(key, modifiers))
+                }
+                _ => None,
+            }),
+        ])
+    }
+
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::Button(button_msg) => match button_msg {
                 ButtonMessage::DownloadVersion => {
+                    if self.launcher_settings.offline {
+                        return self.show_error("Turn off offline mode to download versions");
+                    }
                     let Some(manifest) = self.manifest.clone() else {
-                        eprintln!("Wait for the manifest to finish downloading");
+                        if self.network_unavailable {
+                            return self.show_error("Could not reach the releases server");
+                        }
+                        // A click right after startup is remembered rather than dropped
+                        self.download_queued = true;
                         return Task::none();
                     };
 
-                    let Some(version) = manifest.get_chosen_version(self.chosen_download_version)
-                    else {
-                        eprintln!("Invalid version {}", self.chosen_download_version);
-                        return Task::none();
+                    let Some(version) = manifest.get_chosen_version(
+                        self.chosen_download_version,
+                        &self.launcher_settings.channels,
+                    ) else {
+                        return self.show_error(format!(
+                            "Invalid version {}",
+                            self.chosen_download_version
+                        ));
                     };
+                    if self.version_downloading || self.versions.contains(&version) {
+                        return Task::none();
+                    }
 
-                    self.version_downloading = true;
+                    self.start_download(manifest, version)
+                }
+                ButtonMessage::UpdateToVersion(version) => {
+                    let Some(manifest) = self.manifest.clone() else {
+                        return Task::none();
+                    };
+                    if self.launcher_settings.offline || self.version_downloading {
+                        return Task::none();
+                    }
+                    self.view = View::Download;
+                    self.start_download(manifest, version)
+                }
+                ButtonMessage::RetryDownload => {
+                    let Some(version) = self.last_download_attempt else {
+                        return Task::none();
+                    };
+                    let Some(manifest) = self.manifest.clone() else {
+                        return self.show_error("Wait for the manifest to finish downloading");
+                    };
+                    if self.version_downloading {
+                        return self.show_error("Wait for the current download to finish");
+                    }
+                    // The `.part` file of the failed attempt is kept, so the download picks up
+                    // where it stopped
+                    self.start_download(manifest, version)
+                }
+                ButtonMessage::CancelDownload => {
+                    if let Some(handle) = self.sdl2_install_task.take() {
+                        handle.abort();
+                        let _ = std::fs::remove_file(download::sdl2_temp_path());
+                        self.version_downloading = false;
+                        self.version_download_update = DownloadUpdate::default();
+                        return Task::none();
+                    }
+                    let Some((version, handle)) = self.version_download_task.take() else {
+                        return Task::none();
+                    };
+                    handle.abort();
+
+                    let partial_path = download::download_path(
+                        &self.launcher_settings.game_dir,
+                        self.launcher_settings.download_staging_dir.as_deref(),
+                        version,
+                    );
+                    if let Err(e) = std::fs::remove_file(&partial_path)
+                        && e.kind() != std::io::ErrorKind::NotFound
+                    {
+                        tracing::warn!(
+                            "Failed to remove partial download {:?}: {}",
+                            partial_path,
+                            e
+                        );
+                    }
 
+                    self.version_downloading = false;
+                    self.version_download_update = DownloadUpdate::default();
+                    Task::none()
+                }
+                ButtonMessage::ReinstallSdl2 => {
+                    if self.launcher_settings.offline {
+                        return self.show_error("Turn off offline mode to download SDL2");
+                    }
+                    if self.version_downloading {
+                        return self.show_error("Wait for the current download to finish");
+                    }
+                    let client = match download::build_client(&self.launcher_settings) {
+                        Ok(client) => client,
+                        Err(e) => return self.show_error(e),
+                    };
                     let game_dir = self.launcher_settings.game_dir.clone();
-                    let sender = self
-                        .version_update_sender
-                        .clone()
-                        .expect("Download update sender not set");
+                    let timeout = self.launcher_settings.network_timeout();
 
-                    Task::perform(
-                        Self::download_version(manifest, game_dir, version, sender),
-                        |res| match res {
-                            Ok(v) => Message::VersionDownloaded(v),
-                            Err(e) => Message::VersionDownloadFailed(e),
+                    self.version_downloading = true;
+                    self.version_download_update = DownloadUpdate::default();
+                    self.last_download_attempt = None;
+                    self.view = View::Download;
+                    let (task, handle) = Task::run(
+                        download::reinstall_sdl2(client, game_dir, timeout),
+                        |event| match event {
+                            DownloadEvent::Update(update) => Message::VersionDownloadUpdate(update),
+                            DownloadEvent::Done(result) => Message::Sdl2Installed(result),
                         },
                     )
+                    .abortable();
+                    self.sdl2_install_task = Some(handle);
+                    task
                 }
                 ButtonMessage::RunVersion => {
-                    if let Some(version) = self.get_chosen_downloaded() {
-                        if !self.versions.contains(&version) {
-                            eprintln!("You have not installed this version");
-                        } else {
-                            self.run_version(version).unwrap_or_else(|e| {
-                                eprintln!("Error running version: {}", e);
-                            });
+                    match self.launch_version(self.get_chosen_downloaded()) {
+                        Ok(()) => Task::none(),
+                        Err(e) => self.show_error(e),
+                    }
+                }
+                ButtonMessage::PlayLatest => {
+                    match self.launch_version(self.get_latest_downloaded()) {
+                        Ok(()) => Task::none(),
+                        Err(e) => self.show_error(e),
+                    }
+                }
+                ButtonMessage::UninstallVersion(version) => {
+                    let exec_path = self
+                        .launcher_settings
+                        .game_dir
+                        .join("versions")
+                        .join(version_file_name(version));
+
+                    // macOS app bundles are directories, everything else is a single file
+                    let removed = if exec_path.is_dir() {
+                        std::fs::remove_dir_all(&exec_path)
+                    } else {
+                        std::fs::remove_file(&exec_path)
+                    };
+                    match removed {
+                        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                            return self.show_error(format!(
+                                "Failed to remove version v{}: {}",
+                                version, e
+                            ));
                         }
+                        _ => {}
+                    }
+
+                    self.versions.remove(&version);
+                    if self.chosen_playing_version == VersionChoice::Specific(version) {
+                        self.chosen_playing_version = VersionChoice::Latest;
+                    }
+                    self.save_versions();
+                    self.load_versions()
+                }
+                ButtonMessage::RefreshVersions => {
+                    if let Err(e) = self.scan_versions_dir() {
+                        return self
+                            .show_error(format!("Failed to scan versions directory: {}", e));
+                    }
+                    self.load_versions()
+                }
+                ButtonMessage::InstallFromFile => {
+                    let dialog = rfd::AsyncFileDialog::new().set_title("Choose Mineplace3D Binary");
+
+                    Task::future(dialog.pick_file()).and_then(|file| {
+                        Task::done(Message::LocalBinaryPicked(file.path().to_path_buf()))
+                    })
+                }
+                ButtonMessage::ConfirmLocalInstall => {
+                    let version = match self.input_local_version_content.trim().parse::<Version>() {
+                        Ok(version) => version,
+                        Err(e) => return self.show_error(format!("Invalid version: {}", e)),
+                    };
+                    if self.running_games.contains_key(&version) {
+                        return self.show_error(format!("Version v{} is running", version));
+                    }
+                    let Some(source) = self.pending_local_install.take() else {
+                        return Task::none();
+                    };
+                    Task::perform(
+                        Self::install_local_binary(
+                            source,
+                            self.launcher_settings.game_dir.clone(),
+                            version,
+                        ),
+                        Message::LocalBinaryInstalled,
+                    )
+                }
+                ButtonMessage::CancelLocalInstall => {
+                    self.pending_local_install = None;
+                    Task::none()
+                }
+                ButtonMessage::VerifyVersion(version) => {
+                    let Some(manifest) = self.manifest.clone() else {
+                        return self.show_error("Wait for the manifest to finish downloading");
+                    };
+                    let client = match download::build_client(&self.launcher_settings) {
+                        Ok(client) => client,
+                        Err(e) => return self.show_error(e),
+                    };
+                    self.verifying_versions.insert(version);
+                    Task::perform(
+                        download::verify_version(
+                            client,
+                            manifest,
+                            self.launcher_settings.game_dir.clone(),
+                            version,
+                            self.launcher_settings.github_token.clone(),
+                        ),
+                        move |result| Message::VersionVerified(version, result),
+                    )
+                }
+                ButtonMessage::CopyVersion(version) => Task::batch([
+                    iced::clipboard::write(version.to_string()),
+                    self.show_notice(format!("Copied v{} to the clipboard", version)),
+                ]),
+                ButtonMessage::FinishFirstRun => {
+                    let game_dir = PathBuf::from(self.input_game_dir_content.trim());
+                    match self.finish_first_run(game_dir) {
+                        Ok(()) => self.load_versions(),
+                        Err(e) => self.show_error(e),
+                    }
+                }
+                ButtonMessage::ToggleFavorite(version) => {
+                    let favorites = &mut self.launcher_settings.favorite_versions;
+                    if !favorites.remove(&version) {
+                        favorites.insert(version);
+                    }
+                    if let Err(e) = self.save_launcher_settings() {
+                        tracing::warn!("Failed to save launcher settings: {}", e);
+                    }
+                    Task::none()
+                }
+                ButtonMessage::CopyDiagnostics => Task::batch([
+                    iced::clipboard::write(self.diagnostics()),
+                    self.show_notice("Copied diagnostics to the clipboard"),
+                ]),
+                ButtonMessage::ReinstallVersion(version) => {
+                    let Some(manifest) = self.manifest.clone() else {
+                        return self.show_error("Wait for the manifest to finish downloading");
+                    };
+                    if self.version_downloading {
+                        return self.show_error("Wait for the current download to finish");
+                    }
+                    if self.running_games.contains_key(&version) {
+                        return self
+                            .show_error(format!("Close v{} before reinstalling it", version));
+                    }
+
+                    // The old file must go, or the download would try to resume it
+                    let exec_path = self
+                        .launcher_settings
+                        .game_dir
+                        .join("versions")
+                        .join(version_file_name(version));
+                    let removed = if exec_path.is_dir() {
+                        std::fs::remove_dir_all(&exec_path)
                     } else {
-                        eprintln!("You have no versions downloaded");
+                        std::fs::remove_file(&exec_path)
+                    };
+                    if let Err(e) = removed
+                        && e.kind() != std::io::ErrorKind::NotFound
+                    {
+                        return self
+                            .show_error(format!("Failed to remove version v{}: {}", version, e));
+                    }
+                    // Nor may a partial download left from before
+                    let partial_path = download::download_path(
+                        &self.launcher_settings.game_dir,
+                        self.launcher_settings.download_staging_dir.as_deref(),
+                        version,
+                    );
+                    if let Err(e) = std::fs::remove_file(&partial_path)
+                        && e.kind() != std::io::ErrorKind::NotFound
+                    {
+                        return self.show_error(format!(
+                            "Failed to remove {}: {}",
+                            partial_path.display(),
+                            e
+                        ));
+                    }
+
+                    // versions.json keeps the old record until the download replaces it
+                    self.view = View::Download;
+                    self.start_download(manifest, version)
+                }
+                ButtonMessage::RedownloadVersion(version) => {
+                    self.corrupt_version = None;
+                    self.update(Message::Button(ButtonMessage::ReinstallVersion(version)))
+                }
+                ButtonMessage::CancelRedownload => {
+                    self.corrupt_version = None;
+                    Task::none()
+                }
+                ButtonMessage::RemoveAllVersions => {
+                    if self.version_downloading {
+                        return self.show_error("Wait for the current download to finish");
+                    }
+                    if !self.running_games.is_empty() {
+                        return self.show_error("Close the running games first");
+                    }
+                    self.confirm_remove_all_versions = true;
+                    Task::none()
+                }
+                ButtonMessage::ConfirmRemoveAllVersions => {
+                    self.confirm_remove_all_versions = false;
+                    // Something may have started while the dialog was open
+                    if self.version_downloading || !self.running_games.is_empty() {
+                        return self.show_error("Wait for downloads and games to finish");
+                    }
+                    self.remove_all_versions()
+                }
+                ButtonMessage::CancelRemoveAllVersions => {
+                    self.confirm_remove_all_versions = false;
+                    Task::none()
+                }
+                ButtonMessage::ResetSettings => {
+                    if self.version_downloading || self.game_dir_move_progress.is_some() {
+                        return self.show_error("Wait for the current download or move to finish");
+                    }
+                    self.confirm_reset_settings = true;
+                    Task::none()
+                }
+                ButtonMessage::ConfirmResetSettings => {
+                    self.confirm_reset_settings = false;
+                    if self.version_downloading || self.game_dir_move_progress.is_some() {
+                        return self.show_error("Wait for the current download or move to finish");
                     }
+                    match self.replace_settings(LauncherSettings::default()) {
+                        Ok(task) => Task::batch([task, self.show_notice("Settings reset")]),
+                        Err(e) => self.show_error(e),
+                    }
+                }
+                ButtonMessage::CancelResetSettings => {
+                    self.confirm_reset_settings = false;
+                    Task::none()
+                }
+                ButtonMessage::ShowChangelog(version) => {
+                    self.changelog_version = Some(version);
+                    if self.release_notes.contains_key(&version) {
+                        return Task::none();
+                    }
+                    let Some(manifest) = self.manifest.as_ref() else {
+                        self.changelog_version = None;
+                        return self.show_error("Wait for the manifest to finish downloading");
+                    };
+                    let tag = manifest.release_tag(version);
+                    let client = match download::build_client(&self.launcher_settings) {
+                        Ok(client) => client,
+                        Err(e) => {
+                            self.changelog_version = None;
+                            return self.show_error(e);
+                        }
+                    };
+                    Task::perform(
+                        download::fetch_release_notes(
+                            client,
+                            self.launcher_settings.releases_repo.clone(),
+                            tag,
+                            self.launcher_settings.github_token.clone(),
+                        ),
+                        move |result| Message::ReleaseNotesFetched(version, result),
+                    )
+                }
+                ButtonMessage::CloseChangelog => {
+                    self.changelog_version = None;
+                    Task::none()
+                }
+                ButtonMessage::OpenLauncherRelease => {
+                    let Some((_, url)) = &self.launcher_update else {
+                        return Task::none();
+                    };
+                    match utils::open_url(url) {
+                        Ok(child) => {
+                            self.opened_processes.push(child);
+                            Task::none()
+                        }
+                        Err(e) => self.show_error(format!("Failed to open {}: {}", url, e)),
+                    }
+                }
+                ButtonMessage::DismissLauncherUpdate => {
+                    self.launcher_update = None;
                     Task::none()
                 }
                 ButtonMessage::SwitchTab(new) => {
                     self.view = new;
+                    let size_is_stale = self
+                        .game_dir_size
+                        .as_ref()
+                        .is_none_or(|(dir, _)| *dir != self.launcher_settings.game_dir);
+                    if new == View::Settings && size_is_stale {
+                        self.measure_game_dir()
+                    } else {
+                        Task::none()
+                    }
+                }
+                ButtonMessage::MeasureGameDir => self.measure_game_dir(),
+                ButtonMessage::OpenGameFolder => {
+                    match utils::open_in_file_manager(&self.launcher_settings.game_dir) {
+                        Ok(child) => {
+                            self.opened_processes.push(child);
+                            Task::none()
+                        }
+                        Err(e) => self.show_error(format!("Failed to open game folder: {}", e)),
+                    }
+                }
+                ButtonMessage::OpenLatestLog => {
+                    match self
+                        .latest_log()
+                        .map(|log| utils::open_in_file_manager(&log))
+                    {
+                        Some(Ok(child)) => {
+                            self.opened_processes.push(child);
+                            Task::none()
+                        }
+                        Some(Err(e)) => self.show_error(format!("Failed to open log file: {}", e)),
+                        None => self.show_error("No game logs have been written yet"),
+                    }
+                }
+                ButtonMessage::OpenLauncherLog => {
+                    match utils::open_in_file_manager(&logging::log_path()) {
+                        Ok(child) => {
+                            self.opened_processes.push(child);
+                            Task::none()
+                        }
+                        Err(e) => self.show_error(format!("Failed to open launcher log: {}", e)),
+                    }
+                }
+                ButtonMessage::BrowseGameDir => {
+                    let dialog = rfd::AsyncFileDialog::new()
+                        .set_title("Choose Game Directory")
+                        .set_directory(&self.launcher_settings.game_dir);
+
+                    Task::future(dialog.pick_folder()).and_then(|folder| {
+                        Task::done(Message::Input(InputMessage::GameDirContentChanged(
+                            folder.path().to_string_lossy().to_string(),
+                        )))
+                    })
+                }
+                ButtonMessage::ConfirmGameDirMove => {
+                    let Some(pending) = self.pending_game_dir_move.take() else {
+                        return Task::none();
+                    };
+                    self.game_dir_move_progress = Some(0.0);
+                    Self::move_game_dir(pending.from, pending.to)
+                }
+                ButtonMessage::CancelGameDirMove => {
+                    self.pending_game_dir_move = None;
                     Task::none()
                 }
+                ButtonMessage::AddProfile => {
+                    let name = self.input_profile_name_content.trim().to_string();
+                    if name.is_empty() {
+                        return self.show_error("Enter a name for the new profile");
+                    }
+                    if self
+                        .launcher_settings
+                        .profiles
+                        .iter()
+                        .any(|profile| profile.name == name)
+                    {
+                        return self
+                            .show_error(format!("A profile named {:?} already exists", name));
+                    }
+                    if self.version_downloading || self.game_dir_move_progress.is_some() {
+                        return self.show_error("Wait for the current download or move to finish");
+                    }
+                    // Kept next to the default game directory rather than inside the current one
+                    let game_dir = unique_profile_dir(
+                        &dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")),
+                        &name,
+                        &self.launcher_settings.profiles,
+                    );
+                    // The profile is only added once its directory exists
+                    if let Err(e) = Self::setup_folder_structure(&game_dir) {
+                        return self.show_error(format!(
+                            "Can't write to {}: {}",
+                            game_dir.display(),
+                            e
+                        ));
+                    }
+
+                    self.launcher_settings.profiles.push(Profile {
+                        name: name.clone(),
+                        game_dir,
+                    });
+                    self.input_profile_name_content.clear();
+                    self.switch_profile(&name)
+                }
+                ButtonMessage::RemoveProfile => {
+                    if self.launcher_settings.profiles.len() <= 1 {
+                        return self.show_error("The last profile can't be removed");
+                    }
+                    let removed = self.launcher_settings.active_profile.clone();
+                    let Some(next) = self
+                        .launcher_settings
+                        .profiles
+                        .iter()
+                        .find(|profile| profile.name != removed)
+                        .map(|profile| profile.name.clone())
+                    else {
+                        return Task::none();
+                    };
+                    let switch = self.switch_profile(&next);
+                    // Switching refuses during a download, in which case nothing is removed
+                    if self.launcher_settings.active_profile == next {
+                        self.launcher_settings
+                            .profiles
+                            .retain(|profile| profile.name != removed);
+                        if let Err(e) = self.save_launcher_settings() {
+                            tracing::warn!("Failed to save launcher settings: {}", e);
+                        }
+                    }
+                    switch
+                }
+                ButtonMessage::ExportSettings => {
+                    let dialog = rfd::AsyncFileDialog::new()
+                        .set_title("Export Settings")
+                        .set_file_name("launcher_settings.json");
+
+                    Task::future(dialog.save_file()).and_then(|file| {
+                        Task::done(Message::SettingsExportPicked(file.path().to_path_buf()))
+                    })
+                }
+                ButtonMessage::ImportSettings => {
+                    if self.version_downloading || self.game_dir_move_progress.is_some() {
+                        return self.show_error("Wait for the current download or move to finish");
+                    }
+                    let dialog = rfd::AsyncFileDialog::new()
+                        .set_title("Import Settings")
+                        .add_filter("JSON", &["json"]);
+
+                    Task::future(dialog.pick_file()).and_then(|file| {
+                        Task::done(Message::SettingsImportPicked(file.path().to_path_buf()))
+                    })
+                }
                 ButtonMessage::SaveSettings => {
+                    let releases_repo = self.input_releases_repo_content.trim().to_string();
+                    if !utils::is_valid_repo(&releases_repo)
+                        && utils::local_mirror(&releases_repo).is_none()
+                    {
+                        return self.show_error(format!(
+                            "Releases repository must look like owner/name or be the path of a local mirror, got {:?}",
+                            releases_repo
+                        ));
+                    }
+                    let http_proxy = self.input_http_proxy_content.trim();
+                    if !http_proxy.is_empty()
+                        && let Err(e) = reqwest::Proxy::all(http_proxy)
+                    {
+                        return self.show_error(format!("Invalid HTTP proxy: {}", e));
+                    }
+                    if !matches!(
+                        self.input_network_timeout_content.trim().parse::<u64>(),
+                        Ok(secs) if secs > 0
+                    ) {
+                        return self.show_error(format!(
+                            "Network timeout must be a whole number of seconds, got {:?}",
+                            self.input_network_timeout_content
+                        ));
+                    }
+                    let max_download_speed = self.input_max_download_speed_content.trim();
+                    if !max_download_speed.is_empty()
+                        && !matches!(
+                            max_download_speed.parse::<u64>(),
+                            Ok(kib) if kib > 0 && kib.checked_mul(1024).is_some()
+                        )
+                    {
+                        return self.show_error(format!(
+                            "Download speed limit must be a whole number of KiB/s between 1 and {}, got {:?}",
+                            u64::MAX / 1024,
+                            max_download_speed
+                        ));
+                    }
+                    let download_staging_dir = self.input_download_staging_dir_content.trim();
+                    if !download_staging_dir.is_empty() {
+                        let download_staging_dir = Path::new(download_staging_dir);
+                        if !download_staging_dir.is_absolute() {
+                            return self.show_error(format!(
+                                "Download staging directory must be an absolute path, got {}",
+                                download_staging_dir.display()
+                            ));
+                        }
+                        if let Err(e) = std::fs::create_dir_all(download_staging_dir)
+                            .and_then(|_| utils::check_writable(download_staging_dir))
+                        {
+                            return self.show_error(format!(
+                                "Can't write to {}: {}",
+                                download_staging_dir.display(),
+                                e
+                            ));
+                        }
+                    }
+
                     let new_game_dir = PathBuf::from(&self.input_game_dir_content);
+                    if let Err(e) = self.validate_game_dir(&new_game_dir) {
+                        return self.show_error(e);
+                    }
 
                     if new_game_dir != self.launcher_settings.game_dir {
                         if new_game_dir.exists() {
-                            eprintln!("New game directory already exists: {:?}", new_game_dir);
-                        } else {
-                            std::fs::create_dir_all(&new_game_dir)
-                                .expect("Failed to create new game directory");
-                            if self.launcher_settings.game_dir.exists() {
-                                copy_dir(&self.launcher_settings.game_dir, &new_game_dir)
-                                    .expect("Failed to copy old game directory to new one");
+                            return self.show_error(format!(
+                                "New game directory {} already exists",
+                                new_game_dir.display()
+                            ));
+                        } else if self.launcher_settings.game_dir.exists() {
+                            // The settings are applied once the move is confirmed and the copy
+                            // has finished
+                            let from = self.launcher_settings.game_dir.clone();
+                            match utils::dir_file_count(&from)
+                                .and_then(|files| Ok((files, utils::dir_size(&from)?)))
+                            {
+                                Ok((files, bytes)) => {
+                                    self.pending_game_dir_move = Some(PendingGameDirMove {
+                                        from,
+                                        to: new_game_dir,
+                                        files,
+                                        bytes,
+                                    });
+                                    return Task::none();
+                                }
+                                Err(e) => {
+                                    return self.show_error(format!(
+                                        "Failed to read game directory: {}",
+                                        e
+                                    ));
+                                }
                             }
+                        } else if let Err(e) = std::fs::create_dir_all(&new_game_dir) {
+                            return self.show_error(format!(
+                                "Can't write to {}: {}",
+                                new_game_dir.display(),
+                                e
+                            ));
                         }
                     }
 
-                    self.launcher_settings.game_dir = new_game_dir;
-
-                    let launcher_settings_file = dirs::config_dir()
-                        .unwrap_or_else(|| PathBuf::from("."))
-                        .join("mineplace3d-launcher")
-                        .join("launcher_settings.json");
-                    let settings_data = serde_json::to_string_pretty(&self.launcher_settings)
-                        .expect("Failed to serialize launcher settings");
-                    std::fs::create_dir_all(launcher_settings_file.parent().unwrap())
-                        .expect("Failed to create launcher settings directory");
-                    std::fs::write(launcher_settings_file, settings_data)
-                        .expect("Failed to write launcher settings file");
-
-                    self.view = View::Play;
-                    self.input_game_dir_content = self
-                        .launcher_settings
-                        .game_dir
-                        .to_string_lossy()
-                        .to_string();
-                    println!(
-                        "Settings saved successfully. New game directory: {:?}",
-                        self.launcher_settings.game_dir
-                    );
-                    self.load_versions();
-
-                    Task::none()
+                    self.apply_settings(new_game_dir)
                 }
             },
             Message::Input(input_msg) => match input_msg {
@@ -664,6 +2771,103 @@ impl Launcher {
                     self.input_game_dir_content = new;
                     Task::none()
                 }
+                InputMessage::LaunchArgsContentChanged(new) => {
+                    self.input_launch_args_content = new;
+                    Task::none()
+                }
+                InputMessage::LaunchWrapperContentChanged(new) => {
+                    self.input_launch_wrapper_content = new;
+                    Task::none()
+                }
+                InputMessage::GithubTokenContentChanged(new) => {
+                    self.input_github_token_content = new;
+                    Task::none()
+                }
+                InputMessage::HttpProxyContentChanged(new) => {
+                    self.input_http_proxy_content = new;
+                    Task::none()
+                }
+                InputMessage::NetworkTimeoutContentChanged(new) => {
+                    self.input_network_timeout_content = new;
+                    Task::none()
+                }
+                InputMessage::MaxDownloadSpeedContentChanged(new) => {
+                    self.input_max_download_speed_content = new;
+                    Task::none()
+                }
+                InputMessage::DownloadStagingDirContentChanged(new) => {
+                    self.input_download_staging_dir_content = new;
+                    Task::none()
+                }
+                InputMessage::ReleasesRepoContentChanged(new) => {
+                    self.input_releases_repo_content = new;
+                    Task::none()
+                }
+                InputMessage::FilterChanged(new) => {
+                    self.input_filter_content = new;
+                    Task::none()
+                }
+                InputMessage::LocalVersionContentChanged(new) => {
+                    self.input_local_version_content = new;
+                    Task::none()
+                }
+                InputMessage::ProfileNameContentChanged(new) => {
+                    self.input_profile_name_content = new;
+                    Task::none()
+                }
+            },
+            Message::Checkbox(checkbox_msg) => match checkbox_msg {
+                CheckboxMessage::VerifyChecksumsToggled(checked) => {
+                    self.launcher_settings.verify_checksums = checked;
+                    Task::none()
+                }
+                CheckboxMessage::AutoInstallSdl2Toggled(checked) => {
+                    self.launcher_settings.auto_install_sdl2 = checked;
+                    Task::none()
+                }
+                CheckboxMessage::BundleSdl2Toggled(checked) => {
+                    self.launcher_settings.bundle_sdl2 = checked;
+                    Task::none()
+                }
+                CheckboxMessage::RemoveOldGameDirToggled(checked) => {
+                    self.remove_old_game_dir = checked;
+                    Task::none()
+                }
+                CheckboxMessage::CleanRunToggled(checked) => {
+                    self.clean_run = checked;
+                    Task::none()
+                }
+                CheckboxMessage::KeepCleanGameDirToggled(checked) => {
+                    self.keep_clean_game_dir = checked;
+                    Task::none()
+                }
+                CheckboxMessage::ChannelToggled(stage, checked) => {
+                    if checked {
+                        self.launcher_settings.channels.insert(stage);
+                    } else {
+                        self.launcher_settings.channels.remove(&stage);
+                    }
+                    Task::none()
+                }
+                CheckboxMessage::CheckLauncherUpdatesToggled(checked) => {
+                    self.launcher_settings.check_launcher_updates = checked;
+                    if !checked {
+                        self.launcher_update = None;
+                    }
+                    Task::none()
+                }
+                CheckboxMessage::OfflineToggled(checked) => {
+                    self.launcher_settings.offline = checked;
+                    if checked {
+                        // Everything that reaches the network needs the manifest
+                        self.manifest = None;
+                        self.download_queued = false;
+                        Task::none()
+                    } else {
+                        self.network_unavailable = false;
+                        self.refetch_manifest()
+                    }
+                }
             },
             Message::PickList(pick_list_msg) => match pick_list_msg {
                 PickListMessage::SelectedPlayingVersion(new) => {
@@ -674,28 +2878,45 @@ impl Launcher {
                     self.chosen_download_version = new;
                     Task::none()
                 }
+                PickListMessage::SelectedInstalledSort(new) => {
+                    self.installed_sort = new;
+                    Task::none()
+                }
+                PickListMessage::SelectedTheme(new) => {
+                    self.launcher_settings.theme = new;
+                    Task::none()
+                }
+                PickListMessage::SelectedFont(new) => {
+                    self.launcher_settings.font = new;
+                    Task::none()
+                }
+                PickListMessage::SelectedFontSize(new) => {
+                    self.launcher_settings.font_size = new;
+                    Task::none()
+                }
+                PickListMessage::SelectedDownloadSegments(new) => {
+                    self.launcher_settings.download_segments = new;
+                    Task::none()
+                }
+                PickListMessage::SelectedProfile(name) => self.switch_profile(&name),
             },
             Message::ManifestFetched(m) => {
                 self.manifest = Some(m);
+                self.network_unavailable = false;
+                if std::mem::take(&mut self.download_queued) {
+                    return self.update(Message::Button(ButtonMessage::DownloadVersion));
+                }
                 Task::none()
             }
             Message::ManifestFetchFailed(e) => {
-                panic!("Could not get manifest: {}", e);
+                self.network_unavailable = true;
+                self.download_queued = false;
+                self.show_error(format!("Could not get manifest: {}", e))
             }
             Message::VersionDownloaded(version) => {
-                self.versions.insert(version);
+                self.record_install(version, self.verified_manifest_checksum(version));
                 self.version_downloading = false;
-                let versions_str: HashSet<String> =
-                    self.versions.iter().map(|v| v.to_string()).collect();
-                let versions_data = serde_json::to_string_pretty(&versions_str)
-                    .expect("Failed to serialize versions");
-                let versions_file_path = self
-                    .launcher_settings
-                    .game_dir
-                    .join("versions")
-                    .join("versions.json");
-                std::fs::write(versions_file_path, versions_data)
-                    .expect("Failed to write versions file");
+                self.version_download_task = None;
                 Task::perform(
                     async {
                         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
@@ -703,34 +2924,345 @@ impl Launcher {
                     |_| Message::VersionDownloadClear,
                 )
             }
+            Message::GameDirMeasured(game_dir, result) => {
+                self.measuring_game_dir = false;
+                match result {
+                    Ok(size) => {
+                        self.game_dir_size = Some((game_dir, size));
+                        Task::none()
+                    }
+                    Err(e) => {
+                        self.show_error(format!("Failed to measure {}: {}", game_dir.display(), e))
+                    }
+                }
+            }
+            Message::GameDirMoveProgress(progress) => {
+                if self.game_dir_move_progress.is_some() {
+                    self.game_dir_move_progress = Some(progress);
+                }
+                Task::none()
+            }
+            Message::GameDirMoved(result) => {
+                self.game_dir_move_progress = None;
+                match result {
+                    Ok(new_game_dir) => {
+                        let old_game_dir = self.launcher_settings.game_dir.clone();
+                        // Checked before applying the settings, since loading the versions
+                        // from the new directory drops any whose binary is missing
+                        let binaries_present = self.versions.iter().all(|version| {
+                            new_game_dir
+                                .join("versions")
+                                .join(version_file_name(*version))
+                                .exists()
+                        });
+                        let apply_settings = self.apply_settings(new_game_dir.clone());
+
+                        if !self.remove_old_game_dir {
+                            return apply_settings;
+                        }
+                        if !binaries_present {
+                            return Task::batch([
+                                apply_settings,
+                                self.show_error(
+                                    "Kept the old game directory because some versions are missing from the new one",
+                                ),
+                            ]);
+                        }
+                        match (
+                            std::fs::canonicalize(&old_game_dir),
+                            std::fs::canonicalize(&new_game_dir),
+                        ) {
+                            // Never delete a directory that is, or contains, the new one
+                            (Ok(old), Ok(new)) if !new.starts_with(&old) => Task::batch([
+                                apply_settings,
+                                Task::perform(tokio::fs::remove_dir_all(old), |result| {
+                                    Message::OldGameDirRemoved(result.map_err(|e| e.to_string()))
+                                }),
+                            ]),
+                            _ => apply_settings,
+                        }
+                    }
+                    Err(e) => self.show_error(format!("Failed to move game directory: {}", e)),
+                }
+            }
+            Message::VersionVerified(version, result) => {
+                self.verifying_versions.remove(&version);
+                match result {
+                    Ok(true) => {
+                        self.verified_versions.insert(version);
+                        Task::none()
+                    }
+                    Ok(false) => {
+                        self.verified_versions.remove(&version);
+                        self.corrupt_version = Some(version);
+                        Task::none()
+                    }
+                    Err(e) => self.show_error(format!("Failed to verify v{}: {}", version, e)),
+                }
+            }
+            Message::LauncherReleaseFetched(result) => {
+                match result {
+                    Ok(release) => {
+                        let current = env!("CARGO_PKG_VERSION").parse::<Version>();
+                        match (release.tag_name.parse::<Version>(), current) {
+                            (Ok(latest), Ok(current)) if latest.cmp_semver(&current).is_gt() => {
+                                self.launcher_update = Some((latest, release.html_url));
+                            }
+                            (Err(e), _) => {
+                                tracing::warn!(
+                                    "Skipping launcher release {:?}: {}",
+                                    release.tag_name,
+                                    e
+                                )
+                            }
+                            _ => {}
+                        }
+                    }
+                    // Not worth interrupting anyone over; the next start tries again
+                    Err(e) => tracing::warn!("Failed to check for launcher updates: {}", e),
+                }
+                Task::none()
+            }
+            Message::ReleaseNotesFetched(version, result) => match result {
+                Ok(body) => {
+                    self.release_notes
+                        .insert(version, utils::markdown_to_text(&body));
+                    Task::none()
+                }
+                Err(e) => {
+                    if self.changelog_version == Some(version) {
+                        self.changelog_version = None;
+                    }
+                    self.show_error(format!("Failed to fetch the v{} changelog: {}", version, e))
+                }
+            },
+            Message::SettingsExportPicked(path) => {
+                let settings_data = serde_json::to_string_pretty(&self.launcher_settings)
+                    .expect("Failed to serialize launcher settings");
+                match utils::write_atomic(&path, settings_data) {
+                    Ok(()) => self.show_notice(format!("Exported settings to {}", path.display())),
+                    Err(e) => self.show_error(format!(
+                        "Failed to export settings to {}: {}",
+                        path.display(),
+                        e
+                    )),
+                }
+            }
+            Message::SettingsImportPicked(path) => match self.import_settings(&path) {
+                Ok(task) => task,
+                Err(e) => self.show_error(e),
+            },
+            Message::LocalBinaryPicked(source) => {
+                let file_name = source
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.input_local_version_content = utils::infer_version_from_file_name(&file_name)
+                    .map(|version| version.to_string())
+                    .unwrap_or_default();
+                self.pending_local_install = Some(source);
+                Task::none()
+            }
+            Message::LocalBinaryInstalled(result) => match result {
+                Ok(version) => {
+                    self.record_install(version, None);
+                    Task::none()
+                }
+                Err(e) => self.show_error(e),
+            },
+            Message::CleanGameDirRemoved(result) => match result {
+                Ok(()) => Task::none(),
+                Err(e) => self.show_error(format!("Failed to remove clean game directory: {}", e)),
+            },
+            Message::OldGameDirRemoved(result) => match result {
+                Ok(()) => Task::none(),
+                Err(e) => self.show_error(format!("Failed to remove old game directory: {}", e)),
+            },
+            Message::PollRunningGames => {
+                // Waiting on exited processes reaps them, so they don't pile up as zombies
+                self.opened_processes
+                    .retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+                // A process we can no longer query was most likely killed, so treat it as exited
+                let exited: Vec<Version> = self
+                    .running_games
+                    .iter_mut()
+                    .filter_map(|(version, (child, _))| {
+                        (!matches!(child.try_wait(), Ok(None))).then_some(*version)
+                    })
+                    .collect();
+                let mut tasks = Vec::new();
+                for version in exited {
+                    let Some((_, started_at)) = self.running_games.remove(&version) else {
+                        continue;
+                    };
+                    match self.clean_runs.remove(&version) {
+                        Some((game_dir, false)) => tasks.push(Task::perform(
+                            tokio::fs::remove_dir_all(game_dir),
+                            |result| {
+                                Message::CleanGameDirRemoved(result.map_err(|e| e.to_string()))
+                            },
+                        )),
+                        Some((_, true)) => {}
+                        None => self.record_play_time(version, started_at.elapsed()),
+                    }
+                }
+                Task::batch(tasks)
+            }
+            Message::VersionsChanged => {
+                // The binary being downloaded or copied in would be picked up half-written
+                if self.version_downloading || self.game_dir_move_progress.is_some() {
+                    return Task::none();
+                }
+                self.update(Message::Button(ButtonMessage::RefreshVersions))
+            }
+            Message::WindowResized(size) => {
+                self.launcher_settings.window_size = Some((size.width as u32, size.height as u32));
+                Task::none()
+            }
+            Message::WindowCloseRequested(id) => {
+                // Games outlive the launcher, so count them as played up to now. Clean game
+                // directories are left to the system's temp folder cleanup.
+                for (version, (_, started_at)) in std::mem::take(&mut self.running_games) {
+                    if !self.clean_runs.contains_key(&version) {
+                        self.record_play_time(version, started_at.elapsed());
+                    }
+                }
+                // Without saved settings, the next start asks for the game directory again
+                if self.view != View::FirstRun
+                    && let Err(e) = self.save_window_state()
+                {
+                    tracing::warn!("Failed to save launcher settings: {}", e);
+                }
+                iced::window::close(id)
+            }
             Message::VersionDownloadClear => {
+                // Dismissing a failure instead of retrying it gives up on the partial download
+                if matches!(self.version_download_update, DownloadUpdate::Failed { .. })
+                    && let Some(version) = self.last_download_attempt.take()
+                {
+                    let partial_path = download::download_path(
+                        &self.launcher_settings.game_dir,
+                        self.launcher_settings.download_staging_dir.as_deref(),
+                        version,
+                    );
+                    if let Err(e) = std::fs::remove_file(&partial_path)
+                        && e.kind() != std::io::ErrorKind::NotFound
+                    {
+                        tracing::warn!(
+                            "Failed to remove partial download {:?}: {}",
+                            partial_path,
+                            e
+                        );
+                    }
+                }
                 self.version_download_update = DownloadUpdate::default();
                 Task::none()
             }
             Message::VersionDownloadFailed(error) => {
-                eprintln!("Version download failed: {}", error);
-                self.version_downloading = false;
-                if let DownloadUpdate::Progress { progress, .. } = self.version_download_update {
-                    self.version_download_update = DownloadUpdate::Failed {
-                        last_progress: Some(progress),
-                    };
-                } else {
-                    self.version_download_update = DownloadUpdate::Failed {
-                        last_progress: None,
-                    };
+                self.version_download_task = None;
+                self.fail_download(format!("Version download failed: {}", error))
+            }
+            Message::Sdl2Installed(result) => {
+                self.sdl2_install_task = None;
+                match result {
+                    Ok(()) => {
+                        self.version_downloading = false;
+                        Task::batch([
+                            self.show_notice(format!("{} reinstalled", download::SDL2_FILE_NAME)),
+                            Task::perform(
+                                async {
+                                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                                },
+                                |_| Message::VersionDownloadClear,
+                            ),
+                        ])
+                    }
+                    Err(e) => self.fail_download(format!("SDL2 install failed: {}", e)),
                 }
-                Task::perform(
-                    async {
-                        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-                    },
-                    |_| Message::VersionDownloadClear,
-                )
             }
-            Message::VersionDownloadUpdateReady(sender) => {
-                self.version_update_sender = Some(sender);
+            Message::KeyPressed(key, modifiers) => {
+                use iced::keyboard::key::{Key, Named};
+
+                let shortcut = match (key.as_ref(), modifiers.command()) {
+                    // Only the game directory input reacts to keys here, submitting on Enter
+                    _ if self.view == View::FirstRun => None,
+                    // The Download tab has no inputs of its own, so Enter only belongs to it
+                    // while no dialog (and its inputs) is on top
+                    (Key::Named(Named::Enter), false)
+                        if self.view == View::Download && !self.dialog_open() =>
+                    {
+                        Some(ButtonMessage::DownloadVersion)
+                    }
+                    (Key::Character("r"), true)
+                        if !self
+                            .get_chosen_downloaded()
+                            .is_some_and(|v| self.running_games.contains_key(&v)) =>
+                    {
+                        Some(ButtonMessage::RunVersion)
+                    }
+                    (Key::Character(","), true) => Some(ButtonMessage::SwitchTab(View::Settings)),
+                    (Key::Named(Named::Escape), false) if self.pending_game_dir_move.is_some() => {
+                        Some(ButtonMessage::CancelGameDirMove)
+                    }
+                    (Key::Named(Named::Escape), false) if self.pending_local_install.is_some() => {
+                        Some(ButtonMessage::CancelLocalInstall)
+                    }
+                    (Key::Named(Named::Escape), false) if self.corrupt_version.is_some() => {
+                        Some(ButtonMessage::CancelRedownload)
+                    }
+                    (Key::Named(Named::Escape), false) if self.changelog_version.is_some() => {
+                        Some(ButtonMessage::CloseChangelog)
+                    }
+                    (Key::Named(Named::Escape), false) if self.confirm_remove_all_versions => {
+                        Some(ButtonMessage::CancelRemoveAllVersions)
+                    }
+                    (Key::Named(Named::Escape), false) if self.confirm_reset_settings => {
+                        Some(ButtonMessage::CancelResetSettings)
+                    }
+                    (Key::Named(Named::Escape), false) if self.view == View::Settings => {
+                        Some(ButtonMessage::SwitchTab(View::Play))
+                    }
+                    (Key::Named(Named::Escape), false) if self.view == View::About => {
+                        Some(ButtonMessage::SwitchTab(View::Settings))
+                    }
+                    _ => None,
+                };
+                match shortcut {
+                    Some(button_msg) => self.update(Message::Button(button_msg)),
+                    None => Task::none(),
+                }
+            }
+            Message::ErrorExpired(shown_at) => {
+                // A newer error restarts the timer
+                if self
+                    .last_error
+                    .as_ref()
+                    .is_some_and(|(_, at)| *at == shown_at)
+                {
+                    self.last_error = None;
+                }
+                Task::none()
+            }
+            Message::NoticeExpired(shown_at) => {
+                if self
+                    .last_notice
+                    .as_ref()
+                    .is_some_and(|(_, at)| *at == shown_at)
+                {
+                    self.last_notice = None;
+                }
                 Task::none()
             }
             Message::VersionDownloadUpdate(update) => {
+                // Progress still buffered in the channel after a cancel must not revive the bar
+                if !self.version_downloading
+                    && matches!(
+                        update,
+                        DownloadUpdate::Progress { .. } | DownloadUpdate::Retrying { .. }
+                    )
+                {
+                    return Task::none();
+                }
                 self.version_download_update = update;
                 Task::none()
             }
@@ -739,36 +3271,194 @@ impl Launcher {
 
     fn play_view(&self) -> iced::Element<'_, Message> {
         let mut installed_versions = Column::new();
-        let mut versions: Vec<Version> = self.versions.iter().copied().collect();
-        versions.sort();
-        versions.reverse();
+        let mut versions: Vec<Version> = self
+            .versions
+            .iter()
+            .copied()
+            .filter(|v| self.channel_enabled(*v))
+            .filter(|v| utils::version_matches_filter(*v, &self.input_filter_content))
+            .collect();
+        match self.installed_sort {
+            InstalledSort::NewestFirst => versions.sort_by(|a, b| b.cmp(a)),
+            InstalledSort::OldestFirst => versions.sort(),
+            // Versions without a recorded install time go last
+            InstalledSort::RecentlyInstalled => versions.sort_by(|a, b| {
+                let installed_at = |v: &Version| {
+                    self.version_records
+                        .get(v)
+                        .and_then(|record| record.installed_at)
+                };
+                installed_at(b).cmp(&installed_at(a)).then(b.cmp(a))
+            }),
+            // Versions that were never played go last
+            InstalledSort::RecentlyPlayed => versions.sort_by(|a, b| {
+                let last_played = |v: &Version| {
+                    self.version_records
+                        .get(v)
+                        .and_then(|record| record.last_played)
+                };
+                last_played(b).cmp(&last_played(a)).then(b.cmp(a))
+            }),
+        }
+        // The sort is stable, so favorites keep the chosen order among themselves
+        versions.sort_by_key(|v| !self.launcher_settings.favorite_versions.contains(v));
+        let remote_versions: Vec<Version> = self
+            .manifest
+            .as_ref()
+            .map(|manifest| manifest.versions.keys().copied().collect())
+            .unwrap_or_default();
         let mut dark = false;
         for version in versions {
+            let exec_path = self
+                .launcher_settings
+                .game_dir
+                .join("versions")
+                .join(version_file_name(version));
+            // App bundles are directories, so only plain files get a size
+            let size_text = match std::fs::metadata(&exec_path) {
+                Ok(metadata) if metadata.is_file() => text(utils::bytes_to_human_readable_u64(
+                    metadata.len(),
+                    utils::ByteUnits::platform_default(),
+                ))
+                .size(14),
+                Ok(_) => text("").size(14),
+                Err(_) => text("missing").size(14).style(text::warning),
+            };
+
+            let last_played_text = match self
+                .version_records
+                .get(&version)
+                .and_then(|record| record.last_played)
+            {
+                Some(secs) => text(format!(
+                    "Last played: {}",
+                    utils::relative_time(
+                        std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs)
+                    )
+                )),
+                None => text(""),
+            }
+            .size(14);
+            let play_time_text = match self.play_times.get(&version) {
+                Some(played) => text(format!("{} played", utils::format_duration(*played))),
+                None => text(""),
+            }
+            .size(14);
+
+            let verifying = self.verifying_versions.contains(&version);
+            let verify_button = button(
+                text(if verifying {
+                    "Verifying..."
+                } else if self.verified_versions.contains(&version) {
+                    "Verified"
+                } else {
+                    "Verify"
+                })
+                .size(14),
+            )
+            .padding([2, 8])
+            .style(button::secondary)
+            .on_press_maybe(
+                (!verifying).then_some(Message::Button(ButtonMessage::VerifyVersion(version))),
+            );
+            let copy_button = button(text("Copy").size(14))
+                .padding([2, 8])
+                .style(button::secondary)
+                .on_press(Message::Button(ButtonMessage::CopyVersion(version)));
+            let changelog_button = button(text("Changelog").size(14))
+                .padding([2, 8])
+                .style(button::secondary)
+                .on_press_maybe(
+                    self.manifest
+                        .is_some()
+                        .then_some(Message::Button(ButtonMessage::ShowChangelog(version))),
+                );
+            let reinstall_button = button(text("Reinstall").size(14))
+                .padding([2, 8])
+                .style(button::secondary)
+                .on_press_maybe(
+                    (self.manifest.is_some() && !self.version_downloading)
+                        .then_some(Message::Button(ButtonMessage::ReinstallVersion(version))),
+                );
+            // A later build that's already installed is listed on its own
+            let newer_build: iced::Element<'_, Message> =
+                match newer_build_of(&version, &remote_versions)
+                    .filter(|newer| !self.versions.contains(newer))
+                {
+                    Some(newer) => button(text!("↑ Update to v{}", newer).size(14))
+                        .padding([2, 8])
+                        .style(button::primary)
+                        .on_press_maybe(
+                            (!self.version_downloading)
+                                .then_some(Message::Button(ButtonMessage::UpdateToVersion(newer))),
+                        )
+                        .into(),
+                    None => space().into(),
+                };
+            let favorite = self.launcher_settings.favorite_versions.contains(&version);
+            let favorite_button = button(text(if favorite { "★" } else { "☆" }).size(14))
+                .padding([2, 8])
+                .style(if favorite {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+                .on_press(Message::Button(ButtonMessage::ToggleFavorite(version)));
+            let prerelease_badge: iced::Element<'_, Message> = if version.is_prerelease() {
+                container(text("pre-release").size(12))
+                    .padding([1, 6])
+                    .style(container::rounded_box)
+                    .into()
+            } else {
+                space().into()
+            };
+            let uninstall_button = button(text("Uninstall").size(14))
+                .padding([2, 8])
+                .style(button::danger)
+                .on_press(Message::Button(ButtonMessage::UninstallVersion(version)));
             installed_versions = installed_versions.push(
-                container(text(format!("v{}", version)).size(16))
-                    .padding(5)
-                    .width(iced::Length::Fill)
-                    .style(if dark {
-                        |theme: &Theme| {
-                            let palette = theme.extended_palette();
-
-                            iced::widget::container::Style {
-                                background: Some(palette.success.weak.color.into()),
-                                text_color: Some(palette.success.weak.text),
-                                ..iced::widget::container::Style::default()
-                            }
+                container(
+                    row![
+                        favorite_button,
+                        text(format!("v{}", version)).size(16),
+                        prerelease_badge,
+                        newer_build,
+                        space().width(iced::Fill),
+                        last_played_text,
+                        play_time_text,
+                        size_text,
+                        copy_button,
+                        changelog_button,
+                        verify_button,
+                        reinstall_button,
+                        uninstall_button
+                    ]
+                    .spacing(10)
+                    .align_y(iced::Alignment::Center),
+                )
+                .padding(5)
+                .width(iced::Length::Fill)
+                .style(if dark {
+                    |theme: &Theme| {
+                        let palette = theme.extended_palette();
+
+                        iced::widget::container::Style {
+                            background: Some(palette.success.weak.color.into()),
+                            text_color: Some(palette.success.weak.text),
+                            ..iced::widget::container::Style::default()
                         }
-                    } else {
-                        |theme: &Theme| {
-                            let palette = theme.extended_palette();
+                    }
+                } else {
+                    |theme: &Theme| {
+                        let palette = theme.extended_palette();
 
-                            iced::widget::container::Style {
-                                background: Some(palette.success.base.color.into()),
-                                text_color: Some(palette.success.base.text),
-                                ..iced::widget::container::Style::default()
-                            }
+                        iced::widget::container::Style {
+                            background: Some(palette.success.base.color.into()),
+                            text_color: Some(palette.success.base.text),
+                            ..iced::widget::container::Style::default()
                         }
-                    }),
+                    }
+                }),
             );
             dark = !dark;
         }
@@ -777,6 +3467,7 @@ impl Launcher {
         let mut versions = self
             .versions
             .iter()
+            .filter(|v| self.channel_enabled(**v))
             .map(|v| VersionChoice::Specific(*v))
             .collect::<Vec<VersionChoice>>();
         versions.sort_by(|a, b| b.cmp(a));
@@ -787,18 +3478,153 @@ impl Launcher {
         .padding(10);
         let version_row = row![text("Version: "), version_picker].align_y(iced::Alignment::Center);
 
-        let run_button = button(text("Run Version").width(iced::Fill).center())
+        let profile_names: Vec<String> = self
+            .launcher_settings
+            .profiles
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect();
+        let profile_picker = pick_list(
+            profile_names,
+            Some(self.launcher_settings.active_profile.clone()),
+            |name| Message::PickList(PickListMessage::SelectedProfile(name)),
+        )
+        .padding(10);
+        let profile_row = row![text("Profile: "), profile_picker].align_y(iced::Alignment::Center);
+
+        let running = self
+            .get_chosen_downloaded()
+            .is_some_and(|v| self.running_games.contains_key(&v));
+        let run_button = button(
+            text(if running { "Running..." } else { "Run Version" })
+                .width(iced::Fill)
+                .center(),
+        )
+        .padding(10)
+        .width(iced::Fill)
+        .on_press_maybe((!running).then_some(Message::Button(ButtonMessage::RunVersion)));
+
+        let latest_installed = self.get_latest_downloaded();
+        let latest_running = latest_installed.is_some_and(|v| self.running_games.contains_key(&v));
+        let play_latest_button = button(
+            text(match latest_installed {
+                Some(version) => format!("Play Latest (v{})", version),
+                None => "Play Latest".to_string(),
+            })
+            .width(iced::Fill)
+            .center(),
+        )
+        .padding(10)
+        .width(iced::Fill)
+        .style(button::success)
+        .on_press_maybe(
+            (latest_installed.is_some() && !latest_running)
+                .then_some(Message::Button(ButtonMessage::PlayLatest)),
+        );
+
+        let mut panel_info = column![].spacing(10).width(iced::FillPortion(2));
+
+        if let Some(update) = self.available_update() {
+            let update_button = button(text(format!("Update to v{}", update)))
+                .padding([5, 10])
+                .style(button::success)
+                .on_press_maybe(
+                    (!self.version_downloading)
+                        .then_some(Message::Button(ButtonMessage::UpdateToVersion(update))),
+                );
+            let changelog_button = button(text("Changelog"))
+                .padding([5, 10])
+                .style(button::secondary)
+                .on_press(Message::Button(ButtonMessage::ShowChangelog(update)));
+            let banner = container(
+                row![
+                    text(format!("A new version is available: v{}", update)).size(16),
+                    space().width(iced::Fill),
+                    changelog_button,
+                    update_button
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            )
             .padding(10)
             .width(iced::Fill)
-            .on_press(Message::Button(ButtonMessage::RunVersion));
+            .style(container::rounded_box);
+            panel_info = panel_info.push(banner);
+        }
 
-        let panel_info = column![text("Installed Versions:").size(20), installed_versions]
-            .spacing(10)
-            .width(iced::FillPortion(2));
+        let panel_info = panel_info
+            .push(
+                row![
+                    text("Installed Versions:").size(20),
+                    space().width(iced::Fill),
+                    button(text("Install from file...").size(14))
+                        .padding([2, 8])
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::InstallFromFile)),
+                    button(text("Refresh").size(14))
+                        .padding([2, 8])
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::RefreshVersions)),
+                ]
+                .align_y(iced::Alignment::Center),
+            )
+            .push(
+                row![
+                    text_input("Filter versions", &self.input_filter_content)
+                        .on_input(|value| Message::Input(InputMessage::FilterChanged(value)))
+                        .padding(5)
+                        .size(16),
+                    pick_list(InstalledSort::ALL, Some(self.installed_sort), |sort| {
+                        Message::PickList(PickListMessage::SelectedInstalledSort(sort))
+                    })
+                    .padding(5)
+                    .text_size(16),
+                ]
+                .spacing(10),
+            )
+            // Takes whatever height is left so the buttons beside it stay on screen
+            .push(scrollable(installed_versions).spacing(5).height(iced::Fill));
 
-        let panel_play = column![version_row, space().height(iced::Fill), run_button]
-            .spacing(10)
-            .width(iced::FillPortion(1));
+        let open_folder_button = button(text("Open Game Folder").width(iced::Fill).center())
+            .padding(10)
+            .width(iced::Fill)
+            .style(button::secondary)
+            .on_press(Message::Button(ButtonMessage::OpenGameFolder));
+
+        let open_log_button = button(text("Open Latest Log").width(iced::Fill).center())
+            .padding(10)
+            .width(iced::Fill)
+            .style(button::secondary)
+            .on_press(Message::Button(ButtonMessage::OpenLatestLog));
+
+        let mut clean_run_options = column![
+            checkbox(self.clean_run)
+                .label("Run with a clean game directory")
+                .on_toggle(|checked| Message::Checkbox(CheckboxMessage::CleanRunToggled(checked))),
+        ]
+        .spacing(5);
+        if self.clean_run {
+            clean_run_options = clean_run_options.push(
+                checkbox(self.keep_clean_game_dir)
+                    .label("Keep it after the game exits")
+                    .on_toggle(|checked| {
+                        Message::Checkbox(CheckboxMessage::KeepCleanGameDirToggled(checked))
+                    }),
+            );
+        }
+
+        let panel_play = column![
+            profile_row,
+            version_row,
+            space().height(iced::Fill),
+            open_folder_button,
+            open_log_button,
+            clean_run_options,
+            run_button,
+            play_latest_button
+        ]
+        .spacing(10)
+        .width(iced::FillPortion(1));
 
         row![panel_info, rule::vertical(1), panel_play]
             .spacing(20)
@@ -811,9 +3637,8 @@ impl Launcher {
             let mut versions = manifest
                 .versions
                 .keys()
-                .filter_map(|v| {
-                    (!self.versions.contains(&v)).then_some(VersionChoice::Specific(*v))
-                })
+                .filter(|v| !self.versions.contains(v) && self.channel_enabled(**v))
+                .map(|v| VersionChoice::Specific(*v))
                 .collect::<Vec<VersionChoice>>();
             versions.sort_by(|a, b| b.cmp(a));
             version_options.extend_from_slice(&versions);
@@ -824,9 +3649,32 @@ impl Launcher {
         .padding(10);
         let version_row = row![text("Version: "), version_picker].align_y(iced::Alignment::Center);
 
+        let version_status = match self.manifest.as_ref().and_then(|m| {
+            m.get_chosen_version(
+                self.chosen_download_version,
+                &self.launcher_settings.channels,
+            )
+        }) {
+            Some(version) if self.versions.contains(&version) => {
+                text("Already installed").size(14).style(text::warning)
+            }
+            Some(version) => text(format!("Ready to download v{}", version))
+                .size(14)
+                .style(text::success),
+            None if self.launcher_settings.offline => {
+                text("Offline mode is on, downloads are disabled").size(14)
+            }
+            None if self.network_unavailable => {
+                text("Could not reach the releases server").size(14)
+            }
+            None => text("Waiting for the version list...").size(14),
+        };
+
         let mut download_button = button(
             text(if self.version_downloading {
                 "Downloading..."
+            } else if self.download_queued {
+                "Download starts once the version list loads..."
             } else {
                 "Download Version"
             })
@@ -836,94 +3684,530 @@ impl Launcher {
         .padding(10)
         .width(iced::Fill);
 
-        if !self.version_downloading && self.manifest.is_some() {
-            download_button =
-                download_button.on_press(Message::Button(ButtonMessage::DownloadVersion));
-        }
+        let manifest_pending =
+            self.manifest.is_none() && !self.launcher_settings.offline && !self.network_unavailable;
+        if !self.version_downloading
+            && !self.download_queued
+            && (self.manifest.is_some() || manifest_pending)
+        {
+            download_button =
+                download_button.on_press(Message::Button(ButtonMessage::DownloadVersion));
+        }
+
+        let mut panel_select = column![
+            version_row,
+            version_status,
+            space().height(iced::Fill),
+            download_button
+        ]
+        .spacing(10)
+        .width(iced::FillPortion(1));
+
+        if self.version_downloading {
+            let cancel_button = button(text("Cancel Download").width(iced::Fill).center())
+                .padding(10)
+                .width(iced::Fill)
+                .style(button::danger)
+                .on_press(Message::Button(ButtonMessage::CancelDownload));
+            panel_select = panel_select.push(cancel_button);
+        }
+
+        let mut info = String::new();
+        if let Some(manifest) = self.manifest.as_ref()
+            && let Some(ve) = manifest.get_chosen_version_entry(
+                self.chosen_download_version,
+                &self.launcher_settings.channels,
+            )
+        {
+            use std::fmt::Write;
+            if let Ok(uploaded_on) = chrono::DateTime::parse_from_rfc3339(&ve.uploaded_on) {
+                writeln!(info, "Uploaded on: {}", uploaded_on.to_utc()).unwrap();
+            }
+        }
+        let info = text(info);
+
+        let mut panel_download = column![info, space().height(iced::Fill)]
+            .spacing(10)
+            .width(iced::FillPortion(2));
+
+        // Clears a finished or failed download without waiting for its timer
+        let dismiss_button = button("Dismiss")
+            .padding(10)
+            .style(button::secondary)
+            .on_press(Message::VersionDownloadClear);
+
+        if let DownloadUpdate::Progress {
+            phase,
+            progress,
+            speed,
+            eta_secs,
+        } = self.version_download_update
+        {
+            panel_download = panel_download.push(text(phase.to_string()).size(16));
+            let progress_bar = iced::widget::progress_bar(0.0..=1.0, progress)
+                .length(iced::Length::Fill)
+                .girth(20);
+            panel_download = panel_download.push(progress_bar);
+            if phase != DownloadPhase::Verifying {
+                let progress_text = text(format!(
+                    "{:.1}% • {}/s • ETA {}",
+                    progress * 100.0,
+                    bytes_to_human_readable(speed),
+                    format_eta(eta_secs),
+                ))
+                .size(16);
+                panel_download = panel_download.push(progress_text);
+            }
+        } else if let DownloadUpdate::Retrying { attempt, retries } = self.version_download_update {
+            let progress_text = text(format!("Retrying ({}/{})...", attempt, retries)).size(16);
+            panel_download = panel_download.push(progress_text);
+        } else if let DownloadUpdate::Finished = self.version_download_update {
+            let progress_bar = iced::widget::progress_bar(0.0..=1.0, 1.0)
+                .length(iced::Length::Fill)
+                .girth(20);
+            panel_download = panel_download.push(progress_bar);
+            let progress_text = text("Download Complete!").size(16);
+            panel_download = panel_download.push(progress_text);
+            panel_download = panel_download.push(dismiss_button);
+        } else if let DownloadUpdate::Failed { last_progress } = self.version_download_update {
+            if let Some(progress) = last_progress {
+                let progress_bar = iced::widget::progress_bar(0.0..=1.0, progress)
+                    .length(iced::Length::Fill)
+                    .girth(20)
+                    .style(progress_bar::danger);
+                panel_download = panel_download.push(progress_bar);
+            }
+            let progress_text = text("Download Failed!").size(16);
+            panel_download = panel_download.push(progress_text);
+            let mut buttons = row![dismiss_button].spacing(10);
+            if let Some(version) = self.last_download_attempt {
+                buttons = buttons.push(
+                    button(text!("Retry v{}", version))
+                        .padding(10)
+                        .style(button::success)
+                        .on_press_maybe(
+                            self.manifest
+                                .is_some()
+                                .then_some(Message::Button(ButtonMessage::RetryDownload)),
+                        ),
+                );
+            }
+            panel_download = panel_download.push(buttons);
+        }
+
+        row![panel_select, rule::vertical(1), panel_download]
+            .spacing(20)
+            .into()
+    }
+
+    fn settings_view(&self) -> iced::Element<'_, Message> {
+        let game_dir_input = text_input("Game Directory", &self.input_game_dir_content)
+            .on_input(|value| Message::Input(InputMessage::GameDirContentChanged(value)))
+            .padding(10)
+            .size(20);
+
+        let launch_args_input = text_input("--fullscreen", &self.input_launch_args_content)
+            .on_input(|value| Message::Input(InputMessage::LaunchArgsContentChanged(value)))
+            .padding(10)
+            .size(20);
+
+        let github_token_input = text_input("Optional", &self.input_github_token_content)
+            .on_input(|value| Message::Input(InputMessage::GithubTokenContentChanged(value)))
+            .secure(true)
+            .padding(10)
+            .size(20);
+
+        let launch_wrapper_input = text_input("gamemoderun", &self.input_launch_wrapper_content)
+            .on_input(|value| Message::Input(InputMessage::LaunchWrapperContentChanged(value)))
+            .padding(10)
+            .size(20);
+
+        let http_proxy_input = text_input("Optional", &self.input_http_proxy_content)
+            .on_input(|value| Message::Input(InputMessage::HttpProxyContentChanged(value)))
+            .padding(10)
+            .size(20);
+
+        let network_timeout_input = text_input(
+            &DEFAULT_NETWORK_TIMEOUT_SECS.to_string(),
+            &self.input_network_timeout_content,
+        )
+        .on_input(|value| Message::Input(InputMessage::NetworkTimeoutContentChanged(value)))
+        .padding(10)
+        .size(20);
+
+        let max_download_speed_input =
+            text_input("Unlimited", &self.input_max_download_speed_content)
+                .on_input(|value| {
+                    Message::Input(InputMessage::MaxDownloadSpeedContentChanged(value))
+                })
+                .padding(10)
+                .size(20);
+
+        let download_staging_dir_input = text_input(
+            "Same as the game directory",
+            &self.input_download_staging_dir_content,
+        )
+        .on_input(|value| Message::Input(InputMessage::DownloadStagingDirContentChanged(value)))
+        .padding(10)
+        .size(20);
+
+        let releases_repo_input =
+            text_input(DEFAULT_RELEASES_REPO, &self.input_releases_repo_content)
+                .on_input(|value| Message::Input(InputMessage::ReleasesRepoContentChanged(value)))
+                .padding(10)
+                .size(20);
+
+        let browse_button = button("Browse...")
+            .padding(10)
+            .on_press(Message::Button(ButtonMessage::BrowseGameDir));
+
+        let game_dir_size_text = match &self.game_dir_size {
+            _ if self.measuring_game_dir => "Measuring...".to_string(),
+            Some((dir, size)) if *dir == self.launcher_settings.game_dir => format!(
+                "Size: {}",
+                utils::bytes_to_human_readable_u64(*size, utils::ByteUnits::platform_default())
+            ),
+            _ => "Size: unknown".to_string(),
+        };
+        let game_dir_size_row = row![
+            text(game_dir_size_text).size(14),
+            button(text("Refresh").size(14))
+                .padding([2, 8])
+                .style(button::secondary)
+                .on_press_maybe(
+                    (!self.measuring_game_dir)
+                        .then_some(Message::Button(ButtonMessage::MeasureGameDir)),
+                ),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+
+        let verify_checksums_checkbox = checkbox(self.launcher_settings.verify_checksums)
+            .label("Verify download checksums")
+            .on_toggle(|checked| {
+                Message::Checkbox(CheckboxMessage::VerifyChecksumsToggled(checked))
+            });
+
+        let reinstall_sdl2_button = button(text("Reinstall SDL2").size(14))
+            .padding([2, 8])
+            .style(button::secondary)
+            .on_press_maybe(
+                (!self.version_downloading)
+                    .then_some(Message::Button(ButtonMessage::ReinstallSdl2)),
+            );
+
+        let mut download_checkboxes = column![verify_checksums_checkbox].spacing(10);
+        if cfg!(target_os = "windows") {
+            download_checkboxes = download_checkboxes.push(
+                row![
+                    checkbox(self.launcher_settings.auto_install_sdl2)
+                        .label("Automatically install SDL2.dll")
+                        .on_toggle(|checked| {
+                            Message::Checkbox(CheckboxMessage::AutoInstallSdl2Toggled(checked))
+                        }),
+                    reinstall_sdl2_button,
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center),
+            );
+        } else if cfg!(target_os = "linux") {
+            let mut sdl2_row = row![
+                checkbox(self.launcher_settings.bundle_sdl2)
+                    .label("Use a bundled SDL2 instead of the system's")
+                    .on_toggle(|checked| {
+                        Message::Checkbox(CheckboxMessage::BundleSdl2Toggled(checked))
+                    }),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center);
+            if self.launcher_settings.bundle_sdl2 {
+                sdl2_row = sdl2_row.push(reinstall_sdl2_button);
+            }
+            download_checkboxes = download_checkboxes.push(sdl2_row);
+        }
+
+        let download_checkboxes = download_checkboxes
+            .push(
+                checkbox(self.launcher_settings.offline)
+                    .label("Offline mode")
+                    .on_toggle(|checked| {
+                        Message::Checkbox(CheckboxMessage::OfflineToggled(checked))
+                    }),
+            )
+            .push(
+                checkbox(self.launcher_settings.check_launcher_updates)
+                    .label("Check for launcher updates on startup")
+                    .on_toggle(|checked| {
+                        Message::Checkbox(CheckboxMessage::CheckLauncherUpdatesToggled(checked))
+                    }),
+            );
+
+        let mut channel_checkboxes = row![].spacing(20);
+        for stage in VersionStage::ALL {
+            channel_checkboxes = channel_checkboxes.push(
+                checkbox(self.launcher_settings.channels.contains(&stage))
+                    .label(if stage.is_prerelease() {
+                        format!("{} (pre-release)", stage)
+                    } else {
+                        stage.to_string()
+                    })
+                    .on_toggle(move |checked| {
+                        Message::Checkbox(CheckboxMessage::ChannelToggled(stage, checked))
+                    }),
+            );
+        }
+
+        let save_button = button("Save Settings").padding(10).on_press_maybe(
+            self.game_dir_move_progress
+                .is_none()
+                .then_some(Message::Button(ButtonMessage::SaveSettings)),
+        );
+
+        let open_folder_button = button("Open Game Folder")
+            .padding(10)
+            .on_press(Message::Button(ButtonMessage::OpenGameFolder));
+
+        let open_log_button = button("Open Launcher Log")
+            .padding(10)
+            .style(button::secondary)
+            .on_press(Message::Button(ButtonMessage::OpenLauncherLog));
+
+        let diagnostics_button = button("Copy Diagnostics")
+            .padding(10)
+            .style(button::secondary)
+            .on_press(Message::Button(ButtonMessage::CopyDiagnostics));
 
-        let panel_select = column![version_row, space().height(iced::Fill), download_button]
-            .spacing(10)
-            .width(iced::FillPortion(1));
+        let about_button = button("About")
+            .padding(10)
+            .style(button::secondary)
+            .on_press(Message::Button(ButtonMessage::SwitchTab(View::About)));
 
-        let mut info = String::new();
-        if let Some(manifest) = self.manifest.as_ref()
-            && let Some(ve) = manifest.get_chosen_version_entry(self.chosen_download_version)
-        {
-            use std::fmt::Write;
-            if let Ok(uploaded_on) = chrono::DateTime::parse_from_rfc3339(&ve.uploaded_on) {
-                writeln!(info, "Uploaded on: {}", uploaded_on.to_utc()).unwrap();
-            }
-        }
-        let info = text(info);
+        let profile_name_input = text_input("New profile name", &self.input_profile_name_content)
+            .on_input(|value| Message::Input(InputMessage::ProfileNameContentChanged(value)))
+            .on_submit(Message::Button(ButtonMessage::AddProfile))
+            .padding(10)
+            .size(20);
+        let profiles_row = row![
+            profile_name_input,
+            button("Add Profile")
+                .padding(10)
+                .on_press(Message::Button(ButtonMessage::AddProfile)),
+            button(text!("Remove {}", self.launcher_settings.active_profile))
+                .padding(10)
+                .style(button::danger)
+                .on_press_maybe(
+                    (self.launcher_settings.profiles.len() > 1)
+                        .then_some(Message::Button(ButtonMessage::RemoveProfile)),
+                ),
+        ]
+        .spacing(10);
 
-        let mut panel_download = column![info, space().height(iced::Fill)]
+        let mut settings = column![
+            text("Launcher Settings").size(30),
+            text("Profiles:").size(20),
+            profiles_row,
+            text("Game Directory:").size(20),
+            row![game_dir_input, browse_button].spacing(10),
+            game_dir_size_row,
+            text("Launch Arguments:").size(20),
+            launch_args_input,
+            text("Launch Wrapper (Linux only):").size(20),
+            launch_wrapper_input,
+            text("Releases Repository:").size(20),
+            releases_repo_input,
+            text("GitHub Token:").size(20),
+            github_token_input,
+            text("HTTP Proxy:").size(20),
+            http_proxy_input,
+            text("Network Timeout (seconds):").size(20),
+            network_timeout_input,
+            text("Download Speed Limit (KiB/s):").size(20),
+            max_download_speed_input,
+            text("Download Staging Directory:").size(20),
+            download_staging_dir_input,
+            text("Parallel Download Segments:").size(20),
+            pick_list(
+                DOWNLOAD_SEGMENTS,
+                Some(self.launcher_settings.download_segments),
+                |segments| Message::PickList(PickListMessage::SelectedDownloadSegments(segments)),
+            )
+            .padding(10),
+            download_checkboxes,
+            text("Release Channels:").size(20),
+            channel_checkboxes,
+            text("Theme:").size(20),
+            pick_list(Theme::ALL, Some(&self.launcher_settings.theme), |theme| {
+                Message::PickList(PickListMessage::SelectedTheme(theme))
+            })
+            .padding(10),
+            text("Font:").size(20),
+            row![
+                pick_list(FontChoice::ALL, Some(self.launcher_settings.font), |font| {
+                    Message::PickList(PickListMessage::SelectedFont(font))
+                })
+                .padding(10),
+                pick_list(FONT_SIZES, Some(self.launcher_settings.font_size), |size| {
+                    Message::PickList(PickListMessage::SelectedFontSize(size))
+                })
+                .padding(10),
+                text("Font changes apply after restarting the launcher").size(14),
+            ]
             .spacing(10)
-            .width(iced::FillPortion(2));
+            .align_y(iced::Alignment::Center),
+            row![
+                save_button,
+                open_folder_button,
+                open_log_button,
+                diagnostics_button,
+                about_button
+            ]
+            .spacing(10),
+        ]
+        .spacing(20)
+        .padding(20);
 
-        if let DownloadUpdate::Progress { progress, speed } = self.version_download_update {
-            let progress_bar = iced::widget::progress_bar(0.0..=1.0, progress)
-                .length(iced::Length::Fill)
-                .girth(20);
-            panel_download = panel_download.push(progress_bar);
-            let progress_text = text(format!(
-                "Download Progress: {:.2}%, Speed: {}/s",
-                progress * 100.0,
-                bytes_to_human_readable(speed),
-            ))
-            .size(16);
-            panel_download = panel_download.push(progress_text);
-        } else if let DownloadUpdate::Finished = self.version_download_update {
-            let progress_bar = iced::widget::progress_bar(0.0..=1.0, 1.0)
-                .length(iced::Length::Fill)
-                .girth(20);
-            panel_download = panel_download.push(progress_bar);
-            let progress_text = text("Download Complete!").size(16);
-            panel_download = panel_download.push(progress_text);
-        } else if let DownloadUpdate::Failed { last_progress } = self.version_download_update {
-            if let Some(progress) = last_progress {
-                let progress_bar = iced::widget::progress_bar(0.0..=1.0, progress)
-                    .length(iced::Length::Fill)
-                    .girth(20)
-                    .style(progress_bar::danger);
-                panel_download = panel_download.push(progress_bar);
-            }
-            let progress_text = text("Download Failed!").size(16);
-            panel_download = panel_download.push(progress_text);
+        if let Some(progress) = self.game_dir_move_progress {
+            settings = settings.push(text!("Moving game directory... {:.0}%", progress * 100.0));
+            settings = settings.push(iced::widget::progress_bar(0.0..=1.0, progress).girth(20));
         }
 
-        row![panel_select, rule::vertical(1), panel_download]
-            .spacing(20)
+        settings
+            .push(text("Advanced").size(30))
+            .push(
+                row![
+                    button("Export Settings...")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::ExportSettings)),
+                    button("Import Settings...")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::ImportSettings)),
+                    button("Reset Settings...")
+                        .padding(10)
+                        .style(button::danger)
+                        .on_press(Message::Button(ButtonMessage::ResetSettings)),
+                    button("Remove All Versions...")
+                        .padding(10)
+                        .style(button::danger)
+                        .on_press(Message::Button(ButtonMessage::RemoveAllVersions)),
+                ]
+                .spacing(10),
+            )
+            .push(
+                text!(
+                    "To manually change the game directory, edit the launcher_settings.json file located in {}.",
+                    dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")).join("mineplace3d-launcher").display()
+                ).size(16),
+            )
             .into()
     }
 
-    fn settings_view(&self) -> iced::Element<'_, Message> {
+    /// Explains where a new install will keep its files and lets the user pick another place
+    /// before anything is created.
+    fn first_run_view(&self) -> iced::Element<'_, Message> {
         let game_dir_input = text_input("Game Directory", &self.input_game_dir_content)
             .on_input(|value| Message::Input(InputMessage::GameDirContentChanged(value)))
+            .on_submit(Message::Button(ButtonMessage::FinishFirstRun))
             .padding(10)
             .size(20);
 
-        let save_button = button("Save Settings")
-            .padding(10)
-            .on_press(Message::Button(ButtonMessage::SaveSettings));
-
-        column![
-            text("Launcher Settings").size(30),
-            text("Game Directory:").size(20),
-            game_dir_input,
-            save_button,
-            text("Advanced").size(30),
-            text!(
-                "To manually change the game directory, edit the launcher_settings.json file located in {}.",
-                dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")).join("mineplace3d-launcher").display()
-            ).size(16),
+        let mut first_run = column![
+            text("Welcome to the Mineplace3D Launcher").size(30),
+            text(
+                "The launcher keeps downloaded versions and game logs in a game directory. \
+                 Pick where it should go; a drive with plenty of free space is best. It can be \
+                 moved later from the settings."
+            )
+            .size(16),
+            row![
+                game_dir_input,
+                button("Browse...")
+                    .padding(10)
+                    .on_press(Message::Button(ButtonMessage::BrowseGameDir)),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
         ]
         .spacing(20)
-        .padding(20)
-        .into()
+        .padding(40)
+        .max_width(800);
+        if let Some((error, _)) = &self.last_error {
+            first_run = first_run.push(text(error).size(16).style(text::danger));
+        }
+        first_run
+            .push(
+                button("Create Game Directory")
+                    .padding(10)
+                    .style(button::success)
+                    .on_press(Message::Button(ButtonMessage::FinishFirstRun)),
+            )
+            .into()
+    }
+
+    /// Details about the launcher and the inputs it matches release assets with, for support.
+    fn about_view(&self) -> iced::Element<'_, Message> {
+        let (os, arch) = (utils::platform_os(), utils::platform_arch());
+        let details = [
+            ("Launcher version", env!("CARGO_PKG_VERSION").to_string()),
+            (
+                "Releases repository",
+                self.launcher_settings.releases_repo.clone(),
+            ),
+            (
+                "Game directory",
+                self.launcher_settings.game_dir.display().to_string(),
+            ),
+            ("Platform", os.clone()),
+            ("Architecture", arch.clone()),
+            ("Asset keys", format!("{}_{}, {}", os, arch, os)),
+        ];
+
+        let mut about = column![text("About").size(30)].spacing(10).padding(20);
+        for (label, value) in details {
+            about = about.push(
+                row![
+                    text!("{}:", label).size(16).width(250),
+                    text(value).size(16)
+                ]
+                .spacing(10),
+            );
+        }
+        about
+            .push(
+                button("Back to Settings")
+                    .padding(10)
+                    .style(button::secondary)
+                    .on_press(Message::Button(ButtonMessage::SwitchTab(View::Settings))),
+            )
+            .into()
+    }
+
+    fn theme(&self) -> Theme {
+        self.launcher_settings.theme.clone()
+    }
+
+    /// Shows the download progress in the title so it stays visible while minimized.
+    fn title(&self) -> String {
+        match self.version_download_update {
+            DownloadUpdate::Progress { progress, .. } => {
+                format!("Mineplace3D Launcher — {:.0}%", progress * 100.0)
+            }
+            _ => "Mineplace3D Launcher".to_string(),
+        }
+    }
+
+    /// Scales the whole interface so text comes out at the chosen font size.
+    fn scale_factor(&self) -> f32 {
+        f32::from(self.launcher_settings.font_size) / f32::from(DEFAULT_FONT_SIZE)
     }
 
     fn view(&self) -> iced::Element<'_, Message> {
-        let mut tab_bar = row![].spacing(5);
+        if self.view == View::FirstRun {
+            return container(self.first_run_view()).center(iced::Fill).into();
+        }
+
+        let mut tab_bar = row![].spacing(5).align_y(iced::Alignment::Center);
         for view in View::ALL {
             let btn = button(text(format!("{}", view)).center())
                 .width(100)
@@ -949,28 +4233,795 @@ impl Launcher {
                 );
             tab_bar = tab_bar.push(btn);
         }
+        if self.is_offline() {
+            tab_bar = tab_bar
+                .push(space().width(iced::Fill))
+                .push(text("Offline").size(14).style(text::warning));
+        }
         let content = match self.view {
             View::Play => self.play_view(),
             View::Download => self.download_view(),
             View::Settings => self.settings_view(),
+            View::About => self.about_view(),
+            View::FirstRun => self.first_run_view(),
         };
-        column![tab_bar, rule::horizontal(1), content]
+        let mut layout = column![tab_bar, rule::horizontal(1)]
             .spacing(20)
-            .padding(20)
-            .into()
+            .padding(20);
+        if let Some((version, _)) = &self.launcher_update {
+            layout = layout.push(
+                container(
+                    row![
+                        text!("Launcher update available: v{}", version).size(16),
+                        space().width(iced::Fill),
+                        button(text("Dismiss"))
+                            .padding([5, 10])
+                            .style(button::secondary)
+                            .on_press(Message::Button(ButtonMessage::DismissLauncherUpdate)),
+                        button(text("Open Release Page"))
+                            .padding([5, 10])
+                            .style(button::success)
+                            .on_press(Message::Button(ButtonMessage::OpenLauncherRelease)),
+                    ]
+                    .spacing(10)
+                    .align_y(iced::Alignment::Center),
+                )
+                .padding(10)
+                .width(iced::Fill)
+                .style(container::rounded_box),
+            );
+        }
+        if let Some((error, _)) = &self.last_error {
+            layout = layout.push(text(error).size(16).style(text::danger));
+        } else if let Some((notice, _)) = &self.last_notice {
+            layout = layout.push(text(notice).size(16).style(text::success));
+        }
+        let layout = layout.push(content);
+
+        if let Some(pending) = &self.pending_game_dir_move {
+            stack![layout, self.game_dir_move_dialog(pending)].into()
+        } else if let Some(source) = &self.pending_local_install {
+            stack![layout, self.local_install_dialog(source)].into()
+        } else if let Some(version) = self.corrupt_version {
+            stack![layout, Self::corrupt_version_dialog(version)].into()
+        } else if self.confirm_remove_all_versions {
+            stack![layout, self.remove_all_versions_dialog()].into()
+        } else if self.confirm_reset_settings {
+            stack![layout, Self::reset_settings_dialog()].into()
+        } else if let Some(version) = self.changelog_version {
+            stack![layout, self.changelog_dialog(version)].into()
+        } else {
+            layout.into()
+        }
+    }
+
+    /// Modal asking the user to confirm copying the game directory to its new location.
+    fn game_dir_move_dialog<'a>(
+        &self,
+        pending: &'a PendingGameDirMove,
+    ) -> iced::Element<'a, Message> {
+        let dialog = container(
+            column![
+                text("Move Game Directory").size(24),
+                text!(
+                    "Copy {} files ({}) from {} to {}?",
+                    pending.files,
+                    utils::bytes_to_human_readable_u64(
+                        pending.bytes,
+                        utils::ByteUnits::platform_default()
+                    ),
+                    pending.from.display(),
+                    pending.to.display()
+                )
+                .size(16),
+                checkbox(self.remove_old_game_dir)
+                    .label("Remove old directory after move")
+                    .on_toggle(|checked| {
+                        Message::Checkbox(CheckboxMessage::RemoveOldGameDirToggled(checked))
+                    }),
+                row![
+                    button("Confirm")
+                        .padding(10)
+                        .on_press(Message::Button(ButtonMessage::ConfirmGameDirMove)),
+                    button("Cancel")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::CancelGameDirMove)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(20),
+        )
+        .width(600)
+        .padding(20)
+        .style(container::rounded_box);
+
+        modal(dialog, Message::Button(ButtonMessage::CancelGameDirMove))
+    }
+
+    /// Modal asking which version a locally picked binary should be installed as.
+    fn local_install_dialog<'a>(&self, source: &'a Path) -> iced::Element<'a, Message> {
+        let version = self.input_local_version_content.trim().parse::<Version>();
+        let confirm_label = match &version {
+            Ok(version) if self.versions.contains(version) => format!("Overwrite v{}", version),
+            _ => "Install".to_string(),
+        };
+        let version_status = match &version {
+            Ok(version) if self.versions.contains(version) => text(format!(
+                "v{} is already installed and will be replaced",
+                version
+            ))
+            .size(14)
+            .style(text::warning),
+            Ok(_) => text("").size(14),
+            Err(e) => text(e.clone()).size(14).style(text::danger),
+        };
+
+        let dialog = container(
+            column![
+                text("Install From File").size(24),
+                text!("Install {} as:", source.display()).size(16),
+                text_input("Version", &self.input_local_version_content)
+                    .on_input(
+                        |value| Message::Input(InputMessage::LocalVersionContentChanged(value))
+                    )
+                    .padding(10)
+                    .size(20),
+                version_status,
+                row![
+                    button(text(confirm_label)).padding(10).on_press_maybe(
+                        version
+                            .is_ok()
+                            .then_some(Message::Button(ButtonMessage::ConfirmLocalInstall))
+                    ),
+                    button("Cancel")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::CancelLocalInstall)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(20),
+        )
+        .width(600)
+        .padding(20)
+        .style(container::rounded_box);
+
+        modal(dialog, Message::Button(ButtonMessage::CancelLocalInstall))
+    }
+
+    /// Modal offering to re-download a version that failed verification.
+    fn corrupt_version_dialog<'a>(version: Version) -> iced::Element<'a, Message> {
+        let dialog = container(
+            column![
+                text("Corrupt Installation").size(24),
+                text!(
+                    "v{} doesn't match the published release. Re-download it?",
+                    version
+                )
+                .size(16),
+                row![
+                    button("Re-download")
+                        .padding(10)
+                        .style(button::danger)
+                        .on_press(Message::Button(ButtonMessage::RedownloadVersion(version))),
+                    button("Cancel")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::CancelRedownload)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(20),
+        )
+        .width(600)
+        .padding(20)
+        .style(container::rounded_box);
+
+        modal(dialog, Message::Button(ButtonMessage::CancelRedownload))
+    }
+
+    fn remove_all_versions_dialog(&self) -> iced::Element<'_, Message> {
+        let dialog = container(
+            column![
+                text("Remove All Versions").size(24),
+                text!(
+                    "Delete all {} installed versions from {}? SDL2 is kept.",
+                    self.versions.len(),
+                    self.launcher_settings.game_dir.join("versions").display()
+                )
+                .size(16),
+                row![
+                    button("Remove All")
+                        .padding(10)
+                        .style(button::danger)
+                        .on_press(Message::Button(ButtonMessage::ConfirmRemoveAllVersions)),
+                    button("Cancel")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::CancelRemoveAllVersions)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(20),
+        )
+        .width(600)
+        .padding(20)
+        .style(container::rounded_box);
+
+        modal(
+            dialog,
+            Message::Button(ButtonMessage::CancelRemoveAllVersions),
+        )
+    }
+
+    /// Modal asking the user to confirm restoring the default settings.
+    fn reset_settings_dialog<'a>() -> iced::Element<'a, Message> {
+        let dialog = container(
+            column![
+                text("Reset Settings").size(24),
+                text(
+                    "Restore every setting to its default, including the game directory and \
+                     profiles? Existing game directories and installed versions are left on disk."
+                )
+                .size(16),
+                row![
+                    button("Reset")
+                        .padding(10)
+                        .style(button::danger)
+                        .on_press(Message::Button(ButtonMessage::ConfirmResetSettings)),
+                    button("Cancel")
+                        .padding(10)
+                        .style(button::secondary)
+                        .on_press(Message::Button(ButtonMessage::CancelResetSettings)),
+                ]
+                .spacing(10),
+            ]
+            .spacing(20),
+        )
+        .width(600)
+        .padding(20)
+        .style(container::rounded_box);
+
+        modal(dialog, Message::Button(ButtonMessage::CancelResetSettings))
+    }
+
+    /// Modal showing the release notes of `version`, or that they are still loading.
+    fn changelog_dialog(&self, version: Version) -> iced::Element<'_, Message> {
+        let notes = match self.release_notes.get(&version) {
+            Some(notes) if notes.is_empty() => text("No release notes."),
+            Some(notes) => text(notes),
+            None => text("Loading..."),
+        }
+        .size(16);
+
+        let dialog = container(
+            column![
+                text!("Changelog for v{}", version).size(24),
+                scrollable(notes).height(400),
+                button("Close")
+                    .padding(10)
+                    .style(button::secondary)
+                    .on_press(Message::Button(ButtonMessage::CloseChangelog)),
+            ]
+            .spacing(20),
+        )
+        .width(700)
+        .padding(20)
+        .style(container::rounded_box);
+
+        modal(dialog, Message::Button(ButtonMessage::CloseChangelog))
     }
 }
 
+/// Shows `content` centered over a dimmed backdrop, sending `on_dismiss` when the backdrop is
+/// clicked.
+fn modal<'a>(
+    content: impl Into<iced::Element<'a, Message>>,
+    on_dismiss: Message,
+) -> iced::Element<'a, Message> {
+    opaque(
+        mouse_area(center(opaque(content)).style(|_theme| container::Style {
+            background: Some(iced::Color::from_rgba(0.0, 0.0, 0.0, 0.6).into()),
+            ..container::Style::default()
+        }))
+        .on_press(on_dismiss),
+    )
+}
+
 fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Before anything is logged, so the log output reaches the console too
+    #[cfg(windows)]
+    if !args.is_empty() {
+        cli::attach_parent_console();
+    }
+
+    logging::init();
     rustls::crypto::ring::default_provider()
         .install_default()
         .unwrap();
 
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
+    // The default font can only be set before the window opens
+    let font = LauncherSettings::load()
+        .ok()
+        .flatten()
+        .map(|settings| settings.font)
+        .unwrap_or_default();
+
     iced::application(Launcher::new, Launcher::update, Launcher::view)
-        .theme(iced::theme::Theme::CatppuccinMocha)
-        .default_font(iced::Font::MONOSPACE)
-        .title("Mineplace3D Launcher")
+        .theme(Launcher::theme)
+        .scale_factor(Launcher::scale_factor)
+        .default_font(font.font())
+        .title(Launcher::title)
         .subscription(Launcher::subscription)
         .window_size((1280, 720))
+        .exit_on_close_request(false)
         .run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn launcher(game_dir: &Path) -> Launcher {
+        Launcher::setup_folder_structure(game_dir).unwrap();
+        Launcher::with_settings(LauncherSettings::new(game_dir.to_path_buf()), false)
+    }
+
+    #[test]
+    fn missing_settings_keys_keep_their_defaults() {
+        let settings: LauncherSettings = serde_json::from_value(serde_json::json!({
+            "game_dir": "games/mineplace3d",
+            "offline": true,
+            "network_timeout_secs": 0,
+        }))
+        .unwrap();
+        let defaults = LauncherSettings::new(PathBuf::from("games/mineplace3d"));
+
+        assert!(settings.offline);
+        // Out of range values fall back rather than failing the whole file
+        assert_eq!(settings.network_timeout_secs, defaults.network_timeout_secs);
+        assert_eq!(settings.verify_checksums, defaults.verify_checksums);
+        assert_eq!(settings.releases_repo, defaults.releases_repo);
+        assert_eq!(settings.channels, defaults.channels);
+        assert_eq!(settings.profiles, defaults.profiles);
+        assert_eq!(settings.active_profile, defaults.active_profile);
+        assert_eq!(settings.game_dir, defaults.game_dir);
+    }
+
+    #[test]
+    fn load_versions_prunes_missing_binaries() {
+        let game_dir = temp_dir("prune-versions");
+        let mut launcher = launcher(&game_dir);
+        let versions_dir = game_dir.join("versions");
+        let installed: Version = "0.3.0".parse().unwrap();
+        let phantom: Version = "0.2.2".parse().unwrap();
+        std::fs::write(
+            versions_dir.join("versions.json"),
+            serde_json::to_string(&VersionsFile {
+                schema: utils::VERSIONS_SCHEMA,
+                versions: [installed, phantom]
+                    .into_iter()
+                    .map(|version| InstalledVersion {
+                        version,
+                        ..InstalledVersion::default()
+                    })
+                    .collect(),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        std::fs::write(versions_dir.join(version_file_name(installed)), "").unwrap();
+
+        let _ = launcher.load_versions();
+
+        assert_eq!(launcher.versions, HashSet::from([installed]));
+        let (saved, _) = VersionsFile::parse(
+            &std::fs::read_to_string(versions_dir.join("versions.json")).unwrap(),
+        )
+        .unwrap();
+        let saved: Vec<Version> = saved.versions.iter().map(|record| record.version).collect();
+        assert_eq!(saved, vec![installed]);
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    /// A manifest listing `versions`, with the first one marked as the latest release.
+    fn manifest(versions: &[&str]) -> Manifest {
+        let key = format!("{}_{}", utils::platform_os(), utils::platform_arch());
+        let entries: serde_json::Map<String, serde_json::Value> = versions
+            .iter()
+            .map(|version| {
+                (
+                    version.to_string(),
+                    serde_json::json!({
+                        "files": { key.clone(): "game-binary" },
+                        "uploaded_on": "2024-01-01",
+                        "real_name": null
+                    }),
+                )
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "latest": { "stable": versions.first(), "beta": null, "alpha": null },
+            "base": "https://example.com/releases/download/",
+            "versions": entries,
+        }))
+        .unwrap()
+    }
+
+    // Showing an error starts a timer, so these run inside a runtime
+    #[tokio::test]
+    async fn download_clicked_before_the_manifest_starts_once_it_arrives() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let game_dir = temp_dir("queued-download");
+        let mut launcher = launcher(&game_dir);
+
+        let _ = launcher.update(Message::Button(ButtonMessage::DownloadVersion));
+        assert!(launcher.download_queued);
+        assert!(!launcher.version_downloading);
+        assert_eq!(launcher.last_error, None);
+
+        let _ = launcher.update(Message::ManifestFetched(manifest(&["0.3.0", "0.2.2"])));
+        assert!(!launcher.download_queued);
+        assert!(launcher.version_downloading);
+        assert_eq!(
+            launcher.last_download_attempt,
+            Some("0.3.0".parse().unwrap())
+        );
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn queued_download_is_dropped_when_the_manifest_fails() {
+        let game_dir = temp_dir("queued-download-failed");
+        let mut launcher = launcher(&game_dir);
+
+        let _ = launcher.update(Message::Button(ButtonMessage::DownloadVersion));
+        let _ = launcher.update(Message::ManifestFetchFailed("offline".to_string()));
+        assert!(!launcher.download_queued);
+        assert!(!launcher.version_downloading);
+
+        let _ = launcher.update(Message::Button(ButtonMessage::DownloadVersion));
+        assert!(!launcher.download_queued);
+        assert!(launcher.last_error.is_some());
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn enter_downloads_only_while_no_dialog_is_open() {
+        use iced::keyboard::{Key, Modifiers, key::Named};
+
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let game_dir = temp_dir("enter-download");
+        let mut launcher = launcher(&game_dir);
+        launcher.manifest = Some(manifest(&["0.3.0"]));
+        launcher.view = View::Download;
+        launcher.pending_local_install = Some(game_dir.join("game-binary"));
+
+        let _ = launcher.update(Message::KeyPressed(
+            Key::Named(Named::Enter),
+            Modifiers::empty(),
+        ));
+        assert!(!launcher.version_downloading);
+
+        let _ = launcher.update(Message::KeyPressed(
+            Key::Named(Named::Escape),
+            Modifiers::empty(),
+        ));
+        assert_eq!(launcher.pending_local_install, None);
+        assert_eq!(launcher.view, View::Download);
+
+        let _ = launcher.update(Message::KeyPressed(
+            Key::Named(Named::Enter),
+            Modifiers::empty(),
+        ));
+        assert!(launcher.version_downloading);
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reinstall_removes_the_partial_download() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let game_dir = temp_dir("reinstall-partial");
+        let mut launcher = launcher(&game_dir);
+        launcher.manifest = Some(manifest(&["0.3.0"]));
+        let version = "0.3.0".parse().unwrap();
+        let exec_path = game_dir.join("versions").join(version_file_name(version));
+        let partial_path = download::download_path(&game_dir, None, version);
+        std::fs::write(&exec_path, "old binary").unwrap();
+        std::fs::write(&partial_path, "corrupt").unwrap();
+
+        let _ = launcher.update(Message::Button(ButtonMessage::ReinstallVersion(version)));
+
+        assert!(!exec_path.exists());
+        assert!(!partial_path.exists());
+        assert!(launcher.version_downloading);
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn update_is_ignored_while_a_download_runs() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let game_dir = temp_dir("update-while-downloading");
+        let mut launcher = launcher(&game_dir);
+        launcher.manifest = Some(manifest(&["0.4.0", "0.3.0"]));
+
+        let _ = launcher.update(Message::Button(ButtonMessage::UpdateToVersion(
+            "0.3.0".parse().unwrap(),
+        )));
+        assert!(launcher.version_downloading);
+
+        let _ = launcher.update(Message::Button(ButtonMessage::UpdateToVersion(
+            "0.4.0".parse().unwrap(),
+        )));
+        assert_eq!(
+            launcher.last_download_attempt,
+            Some("0.3.0".parse().unwrap())
+        );
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn latest_skips_hidden_channels() {
+        let game_dir = temp_dir("latest-channels");
+        let mut launcher = launcher(&game_dir);
+        let alpha: Version = "0.4.0-alpha.1".parse().unwrap();
+        let stable: Version = "0.3.0".parse().unwrap();
+        let mut manifest = manifest(&["0.3.0", "0.4.0-alpha.1"]);
+        manifest.latest.alpha = Some(alpha);
+        install(&mut launcher, &["0.4.0-alpha.1"]);
+
+        let channels = &launcher.launcher_settings.channels;
+        assert_eq!(
+            manifest.get_chosen_version(VersionChoice::Latest, channels),
+            Some(stable)
+        );
+        assert_eq!(launcher.get_latest_downloaded(), Some(alpha));
+
+        launcher.launcher_settings.channels = HashSet::from([VersionStage::Release]);
+        manifest.latest.stable = None;
+        let channels = &launcher.launcher_settings.channels;
+        assert_eq!(
+            manifest.get_chosen_version(VersionChoice::Latest, channels),
+            None
+        );
+        assert_eq!(launcher.get_latest_downloaded(), None);
+
+        install(&mut launcher, &["0.3.0"]);
+        assert_eq!(launcher.get_latest_downloaded(), Some(stable));
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn sdl2_progress_updates_reach_the_download_state() {
+        let game_dir = temp_dir("sdl2-progress");
+        let mut launcher = launcher(&game_dir);
+        launcher.version_downloading = true;
+
+        let _ = launcher.update(Message::VersionDownloadUpdate(DownloadUpdate::new(
+            DownloadPhase::Sdl2,
+            0.5,
+            0.0,
+            0.0,
+        )));
+        assert!(matches!(
+            launcher.version_download_update,
+            DownloadUpdate::Progress {
+                phase: DownloadPhase::Sdl2,
+                ..
+            }
+        ));
+
+        let _ = launcher.update(Message::VersionDownloadUpdate(DownloadUpdate::Finished));
+        assert!(matches!(
+            launcher.version_download_update,
+            DownloadUpdate::Finished
+        ));
+
+        // Progress arriving after the download stopped is stale
+        launcher.version_downloading = false;
+        let _ = launcher.update(Message::VersionDownloadUpdate(DownloadUpdate::new(
+            DownloadPhase::Sdl2,
+            0.9,
+            0.0,
+            0.0,
+        )));
+        assert!(matches!(
+            launcher.version_download_update,
+            DownloadUpdate::Finished
+        ));
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    fn install(launcher: &mut Launcher, versions: &[&str]) {
+        for version in versions {
+            launcher.versions.insert(version.parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn newer_prerelease_is_an_available_update() {
+        let game_dir = temp_dir("update-prerelease");
+        let mut launcher = launcher(&game_dir);
+        install(&mut launcher, &["0.3.0", "0.2.2"]);
+        launcher.manifest = Some(manifest(&["0.4.0-alpha.1", "0.3.0"]));
+        assert_eq!(
+            launcher.available_update(),
+            Some("0.4.0-alpha.1".parse().unwrap())
+        );
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn older_release_is_not_an_available_update() {
+        let game_dir = temp_dir("update-older");
+        let mut launcher = launcher(&game_dir);
+        install(&mut launcher, &["0.4.0-alpha.1"]);
+        launcher.manifest = Some(manifest(&["0.3.0"]));
+        assert_eq!(launcher.available_update(), None);
+
+        install(&mut launcher, &["0.3.0"]);
+        assert_eq!(launcher.available_update(), None);
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn launcher_update_compares_in_semver_order() {
+        let game_dir = temp_dir("launcher-update");
+        let mut launcher = launcher(&game_dir);
+        let release = |tag: String| {
+            Message::LauncherReleaseFetched(Ok(download::LatestRelease {
+                tag_name: tag,
+                html_url: "https://example.com/release".to_string(),
+            }))
+        };
+
+        // A pre-release of an older version is older, even though its stage sorts first
+        let _ = launcher.update(release("v0.0.1-beta.1".to_string()));
+        assert_eq!(launcher.launcher_update, None);
+
+        let next_minor = format!(
+            "{}.{}.0-alpha.1",
+            env!("CARGO_PKG_VERSION_MAJOR"),
+            env!("CARGO_PKG_VERSION_MINOR").parse::<u32>().unwrap() + 1
+        );
+        let _ = launcher.update(release(format!("v{}", next_minor)));
+        assert_eq!(
+            launcher.launcher_update.map(|(version, _)| version),
+            Some(next_minor.parse().unwrap())
+        );
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn save_settings_rejects_an_overflowing_speed_limit() {
+        let game_dir = temp_dir("speed-limit");
+        let mut launcher = launcher(&game_dir);
+        launcher.input_max_download_speed_content = (u64::MAX / 1024 + 1).to_string();
+
+        let _ = launcher.update(Message::Button(ButtonMessage::SaveSettings));
+
+        let (error, _) = launcher.last_error.clone().unwrap();
+        assert!(error.starts_with("Download speed limit"), "{}", error);
+        assert_eq!(launcher.launcher_settings.max_download_bytes_per_sec, None);
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn profile_dirs_with_the_same_name_get_a_suffix() {
+        let parent = temp_dir("profile-dirs");
+        let mut profiles = Vec::new();
+        for name in ["My Game", "My-Game", "my game"] {
+            let game_dir = unique_profile_dir(&parent, name, &profiles);
+            profiles.push(Profile {
+                name: name.to_string(),
+                game_dir,
+            });
+        }
+        let dirs: Vec<PathBuf> = profiles.iter().map(|p| p.game_dir.clone()).collect();
+        assert_eq!(
+            dirs,
+            [
+                parent.join("mineplace3d-my-game"),
+                parent.join("mineplace3d-my-game-2"),
+                parent.join("mineplace3d-my-game-3"),
+            ]
+        );
+
+        // Directories left behind by a removed profile aren't reused either
+        std::fs::create_dir_all(parent.join("mineplace3d-other")).unwrap();
+        assert_eq!(
+            unique_profile_dir(&parent, "Other", &[]),
+            parent.join("mineplace3d-other-2")
+        );
+
+        std::fs::remove_dir_all(parent).unwrap();
+    }
+
+    #[test]
+    fn installing_two_spellings_of_a_version_keeps_one_entry() {
+        let game_dir = temp_dir("install-spellings");
+        let mut launcher = launcher(&game_dir);
+        for spelling in ["0.3.0", "0.3.0-release"] {
+            let version: Version = spelling.parse().unwrap();
+            std::fs::write(
+                game_dir.join("versions").join(version_file_name(version)),
+                "",
+            )
+            .unwrap();
+            launcher.record_install(version, None);
+        }
+
+        assert_eq!(launcher.versions.len(), 1);
+        assert_eq!(launcher.version_records.len(), 1);
+        let (saved, _) = VersionsFile::parse(
+            &std::fs::read_to_string(game_dir.join("versions").join("versions.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(saved.versions.len(), 1);
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn scanning_ignores_non_canonical_file_names() {
+        let game_dir = temp_dir("scan-spellings");
+        let mut launcher = launcher(&game_dir);
+        let version: Version = "0.3.0".parse().unwrap();
+        let versions_dir = game_dir.join("versions");
+        std::fs::write(versions_dir.join(version_file_name(version)), "").unwrap();
+        let stray = version_file_name(version).replacen("0.3.0", "0.3.0-release", 1);
+        std::fs::write(versions_dir.join(&stray), "").unwrap();
+
+        launcher.scan_versions_dir().unwrap();
+
+        assert_eq!(launcher.versions, HashSet::from([version]));
+        // The stray file is left alone rather than renamed or deleted
+        assert!(versions_dir.join(stray).exists());
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn polling_reaps_exited_opened_processes() {
+        let game_dir = temp_dir("reap-opened");
+        let mut launcher = launcher(&game_dir);
+        let exiting = std::process::Command::new("true").spawn().unwrap();
+        let sleeping = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let sleeping_id = sleeping.id();
+        launcher.opened_processes = vec![exiting, sleeping];
+        std::thread::sleep(std::time::Duration::from_millis(200));
+
+        let _ = launcher.update(Message::PollRunningGames);
+
+        let ids: Vec<u32> = launcher.opened_processes.iter().map(|c| c.id()).collect();
+        assert_eq!(ids, [sleeping_id]);
+        let mut sleeping = launcher.opened_processes.pop().unwrap();
+        sleeping.kill().unwrap();
+        sleeping.wait().unwrap();
+
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+}
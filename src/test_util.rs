@@ -0,0 +1,15 @@
+//! Fixtures shared by the test modules.
+
+use std::path::PathBuf;
+
+/// An empty directory for one test, removed again by the test itself.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "mineplace3d-launcher-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -1,26 +1,259 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::VersionChoice;
-use crate::version::Version;
+use crate::version::{Version, VersionRange, VersionStage};
 
-pub fn copy_dir(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
+/// Recursively copies `src` into `dst`, calling `on_progress(copied, total)` after each file.
+///
+/// Both values are in bytes; `total` is measured up front with [`dir_size`].
+/// Symlinks are recreated rather than followed, and permissions are carried over.
+pub fn copy_dir(
+    src: &PathBuf,
+    dst: &PathBuf,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> std::io::Result<()> {
+    let total = dir_size(src)?;
+    let mut copied = 0;
+    copy_dir_inner(src, dst, &mut copied, total, on_progress)
+}
+
+fn copy_dir_inner(
+    src: &PathBuf,
+    dst: &PathBuf,
+    copied: &mut u64,
+    total: u64,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> std::io::Result<()> {
     std::fs::create_dir_all(dst)?;
     for entry in std::fs::read_dir(src)? {
         let entry = entry?;
         let file_type = entry.file_type()?;
         let dest_path = dst.join(entry.file_name());
-        if file_type.is_dir() {
-            copy_dir(&entry.path(), &dest_path)?;
+        if file_type.is_symlink() {
+            copy_symlink(&entry.path(), &dest_path)?;
+        } else if file_type.is_dir() {
+            copy_dir_inner(&entry.path(), &dest_path, copied, total, on_progress)?;
+            std::fs::set_permissions(&dest_path, entry.metadata()?.permissions())?;
         } else {
-            std::fs::copy(entry.path(), &dest_path)?;
+            *copied += std::fs::copy(entry.path(), &dest_path)?;
+            std::fs::set_permissions(&dest_path, entry.metadata()?.permissions())?;
+            on_progress(*copied, total);
         }
     }
     Ok(())
 }
 
+/// Recreates the symlink at `src` as `dst`, pointing at the same target.
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(src)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, dst)
+    }
+
+    #[cfg(windows)]
+    {
+        if std::fs::metadata(src).is_ok_and(|metadata| metadata.is_dir()) {
+            std::os::windows::fs::symlink_dir(target, dst)
+        } else {
+            std::os::windows::fs::symlink_file(target, dst)
+        }
+    }
+}
+
+/// Returns the number of files under `path`, not counting directories or symlinks.
+pub fn dir_file_count(path: &Path) -> std::io::Result<u64> {
+    let mut count = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            count += dir_file_count(&entry.path())?;
+        } else if file_type.is_file() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Returns the combined size in bytes of all files under `path`.
+pub fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            continue;
+        } else if file_type.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else {
+            size += entry.metadata()?.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Opens `path` in the platform's file manager without waiting for it to close.
+///
+/// Files are opened with whatever application the platform associates with them. The returned
+/// process has to be waited on once it exits so it doesn't linger as a zombie.
+pub fn open_in_file_manager(path: &Path) -> std::io::Result<std::process::Child> {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    std::process::Command::new(program).arg(path).spawn()
+}
+
+/// Opens `url` in the default web browser without waiting for it to close, returning the
+/// process that has to be waited on like [`open_in_file_manager`]'s.
+pub fn open_url(url: &str) -> std::io::Result<std::process::Child> {
+    let mut command = if cfg!(target_os = "windows") {
+        // `start` treats its first quoted argument as the window title
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+
+    command.arg(url).spawn()
+}
+
+/// Splits a command line into arguments on whitespace, keeping single- or double-quoted
+/// sections together.
+///
+/// Inside double quotes `\"` and `\\` stand for a literal quote and backslash, as in a POSIX
+/// shell. Backslashes anywhere else are kept as they are, so Windows paths need no escaping.
+pub fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some('"') if c == '\\' && matches!(chars.peek(), Some('"' | '\\')) => {
+                current.extend(chars.next());
+            }
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+/// The command that starts the game binary at `exec_path`, run through `wrapper` like
+/// `gamemoderun` or `mangohud --dlsym` when one is given.
+///
+/// Wrappers are only supported on Linux and are ignored elsewhere.
+#[cfg(not(target_os = "macos"))]
+pub fn game_command(exec_path: &Path, wrapper: Option<&str>) -> std::process::Command {
+    let wrapper = if cfg!(target_os = "linux") {
+        wrapper.map(split_args).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    match wrapper.split_first() {
+        Some((program, args)) => {
+            let mut command = std::process::Command::new(program);
+            command.args(args).arg(exec_path);
+            command
+        }
+        None => std::process::Command::new(exec_path),
+    }
+}
+
+/// Joins arguments back into a command line that [`split_args`] parses to the same list.
+///
+/// Arguments that are empty or contain whitespace or quotes are double-quoted, with quotes and
+/// backslashes inside them escaped.
+pub fn join_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Which multiple of bytes [`bytes_to_human_readable_u64`] steps through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteUnits {
+    /// Powers of 1000: kB, MB, GB, ...
+    Si,
+    /// Powers of 1024: KiB, MiB, GiB, ...
+    Iec,
+}
+
+impl ByteUnits {
+    /// The units the platform's own file manager shows sizes in.
+    pub fn platform_default() -> Self {
+        if cfg!(target_os = "macos") {
+            ByteUnits::Si
+        } else {
+            ByteUnits::Iec
+        }
+    }
+}
+
+pub fn bytes_to_human_readable_u64(bytes: u64, units: ByteUnits) -> String {
+    let (step, labels) = match units {
+        ByteUnits::Si => (1000, ["B", "kB", "MB", "GB", "TB"]),
+        ByteUnits::Iec => (1024, ["B", "KiB", "MiB", "GiB", "TiB"]),
+    };
+    if bytes < step {
+        return format!("{} B", bytes);
+    }
+
+    // Divide in integers first so large sizes don't lose precision in the float conversion
+    let mut unit_index = 1;
+    let mut whole = bytes / step;
+    let mut divisor = step;
+    while whole >= step && unit_index < labels.len() - 1 {
+        whole /= step;
+        divisor *= step;
+        unit_index += 1;
+    }
+    let size = whole as f64 + (bytes % divisor) as f64 / divisor as f64;
+    format!("{:.2} {}", size, labels[unit_index])
+}
+
 pub fn bytes_to_human_readable(bytes: f32) -> String {
     const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes;
@@ -32,50 +265,569 @@ pub fn bytes_to_human_readable(bytes: f32) -> String {
     format!("{:.2} {}", size, UNITS[unit_index])
 }
 
-pub fn platform_key() -> String {
-    let os = if cfg!(target_os = "windows") {
-        "windows"
-    } else if cfg!(target_os = "linux") {
-        "linux"
-    } else if cfg!(target_os = "macos") {
-        "macos"
+/// Formats the estimated seconds left on a download as `m:ss`, or `h:mm:ss` once it reaches an
+/// hour.
+pub fn format_eta(secs: f32) -> String {
+    let total = secs.max(0.0).round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Formats a duration such as time spent playing as `Xh Ym`, leaving out the hours while there
+/// are none.
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let total_minutes = duration.as_secs() / 60;
+    let (hours, minutes) = (total_minutes / 60, total_minutes % 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
     } else {
-        "unknown"
+        format!("{}m", minutes)
+    }
+}
+
+/// Describes how long ago `since` was, like `5 minutes ago` or `2 days ago`.
+///
+/// Times in the future, as left behind by a clock change, count as just now.
+pub fn relative_time(since: std::time::SystemTime) -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(since)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let (amount, unit) = match secs {
+        0..60 => return "just now".to_string(),
+        60..3600 => (secs / 60, "minute"),
+        3600..86400 => (secs / 3600, "hour"),
+        _ => (secs / 86400, "day"),
+    };
+    if amount == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", amount, unit)
+    }
+}
+
+/// Turns Markdown release notes into plain text, dropping the markup that would otherwise show
+/// up literally: heading markers, emphasis, inline code and link targets.
+pub fn markdown_to_text(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            let line = line.trim_end();
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            let line = if let Some(heading) = trimmed.strip_prefix('#') {
+                heading.trim_start_matches('#').trim_start().to_string()
+            } else if let Some(item) = trimmed
+                .strip_prefix("- ")
+                .or_else(|| trimmed.strip_prefix("* "))
+            {
+                format!("{}• {}", indent, item)
+            } else {
+                line.to_string()
+            };
+
+            // `[text](url)` keeps only its text
+            let mut plain = String::with_capacity(line.len());
+            let mut rest = line.as_str();
+            while let Some(start) = rest.find('[') {
+                let link = rest[start + 1..]
+                    .split_once("](")
+                    .and_then(|(text, after)| {
+                        let (_, after) = after.split_once(')')?;
+                        (!text.contains('[')).then_some((text, after))
+                    });
+                match link {
+                    Some((text, after)) => {
+                        plain.push_str(&rest[..start]);
+                        plain.push_str(text);
+                        rest = after;
+                    }
+                    None => {
+                        plain.push_str(&rest[..=start]);
+                        rest = &rest[start + 1..];
+                    }
+                }
+            }
+            plain.push_str(rest);
+            plain.replace("**", "").replace("__", "").replace('`', "")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// The hidden temporary file next to `path` that it is written through, like `.name.tmp`.
+fn temp_sibling(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "No file name"))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    Ok(path.with_file_name(temp_name))
+}
+
+/// Replaces the file at `path` with `contents` so that it is never left half-written.
+///
+/// The data goes to a temporary file next to `path` first, which is then renamed over it.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let temp_path = temp_sibling(path)?;
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Moves the file at `from` to `to`, copying it when they are on different filesystems.
+///
+/// A copy goes to a temporary file next to `to` first, so `to` never exists half-written.
+pub fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    match std::fs::rename(from, to) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            let temp_path = temp_sibling(to)?;
+            let result =
+                std::fs::copy(from, &temp_path).and_then(|_| std::fs::rename(&temp_path, to));
+            if result.is_err() {
+                let _ = std::fs::remove_file(&temp_path);
+            }
+            result?;
+            std::fs::remove_file(from)
+        }
+        result => result,
+    }
+}
+
+/// Free space the launcher may use on the filesystem holding `path`, in bytes, or `None` if
+/// the platform can't tell.
+pub fn available_space(path: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+        // SAFETY: `path` is NUL-terminated and `stats` is only read after a successful call
+        // filled it in
+        if unsafe { libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        let stats = unsafe { stats.assume_init() };
+        #[allow(clippy::unnecessary_cast)]
+        Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let mut free_to_caller = 0u64;
+        // SAFETY: `wide` is NUL-terminated and the unused out-pointers may be null
+        let ok = unsafe {
+            windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW(
+                wide.as_ptr(),
+                &mut free_to_caller,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        (ok != 0).then_some(free_to_caller)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Checks that files can be created in the directory `dir` by writing and removing a probe file.
+pub fn check_writable(dir: &Path) -> std::io::Result<()> {
+    let probe_path = dir.join(".mineplace3d-write-test");
+    std::fs::write(&probe_path, b"")?;
+    std::fs::remove_file(probe_path)
+}
+
+/// Marks the file at `path` as executable by everyone. Does nothing on platforms without
+/// permission bits.
+pub fn set_executable(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(path, perms)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Computes the lowercase hex SHA-256 digest of the file at `path`.
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Whether `repo` has the `owner/name` shape of a GitHub repository.
+pub fn is_valid_repo(repo: &str) -> bool {
+    let valid_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
     };
+    matches!(repo.split_once('/'), Some((owner, name)) if valid_part(owner) && valid_part(name))
+}
+
+/// The directory of a local release mirror when `repo` is a `file://` URL or an absolute path
+/// instead of a GitHub `owner/name`.
+pub fn local_mirror(repo: &str) -> Option<PathBuf> {
+    if repo.starts_with("file://") {
+        local_file_path(repo)
+    } else {
+        Some(PathBuf::from(repo)).filter(|path| path.is_absolute())
+    }
+}
+
+/// The path a `file://` URL points to, or `None` for any other URL.
+pub fn local_file_path(url: &str) -> Option<PathBuf> {
+    reqwest::Url::parse(url)
+        .ok()
+        .filter(|url| url.scheme() == "file")
+        .and_then(|url| url.to_file_path().ok())
+}
+
+/// Directories searched for shared libraries the linker cache doesn't know about:
+/// `LD_LIBRARY_PATH` followed by the usual system library directories.
+#[cfg(target_os = "linux")]
+pub fn library_search_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("LD_LIBRARY_PATH")
+        .map(|paths| std::env::split_paths(&paths).collect())
+        .unwrap_or_default();
+    let multiarch = format!("/usr/lib/{}-linux-gnu", std::env::consts::ARCH);
+    for dir in [
+        "/usr/local/lib",
+        "/usr/local/lib64",
+        multiarch.as_str(),
+        "/usr/lib64",
+        "/usr/lib",
+        "/lib64",
+        "/lib",
+    ] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs.retain(|dir| !dir.as_os_str().is_empty());
+    dirs
+}
+
+/// Whether `file_name` is a shared object of the library `name`, like `libSDL2-2.0.so.0` for
+/// `libSDL2`, without matching other libraries that share the prefix such as `libSDL2_ttf`.
+#[cfg(target_os = "linux")]
+pub fn is_shared_library(file_name: &str, name: &str) -> bool {
+    file_name.strip_prefix(name).is_some_and(|rest| {
+        (rest.starts_with('-') || rest.starts_with('.'))
+            && (rest.ends_with(".so") || rest.contains(".so."))
+    })
+}
+
+/// The first shared object of the library `name` in `dirs`, checked in order.
+#[cfg(target_os = "linux")]
+pub fn find_library(dirs: &[PathBuf], name: &str) -> Option<PathBuf> {
+    dirs.iter().find_map(|dir| {
+        std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .find(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|file_name| is_shared_library(file_name, name))
+            })
+            .map(|entry| entry.path())
+    })
+}
+
+/// URL of the manifest published on the GitHub Pages site of `repo`.
+pub fn manifest_url(repo: &str) -> String {
+    let (owner, name) = repo.split_once('/').unwrap_or((repo, ""));
+    format!(
+        "https://{}.github.io/{}/manifest.json",
+        owner.to_lowercase(),
+        name
+    )
+}
+
+/// The operating system part of the manifest's platform keys.
+///
+/// `MINEPLACE3D_FORCE_OS` replaces the OS the launcher was built for.
+pub fn platform_os() -> String {
+    env_override("MINEPLACE3D_FORCE_OS").unwrap_or_else(|| std::env::consts::OS.to_string())
+}
+
+/// The CPU architecture part of the manifest's platform keys.
+///
+/// `MINEPLACE3D_FORCE_ARCH` replaces the architecture the launcher was built for, for example
+/// to pick an x86_64 build under Rosetta.
+pub fn platform_arch() -> String {
+    env_override("MINEPLACE3D_FORCE_ARCH").unwrap_or_else(|| std::env::consts::ARCH.to_string())
+}
+
+fn env_override(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Picks the file built for `os` and `arch` out of a manifest entry's `files`, returning its
+/// platform key and file name.
+///
+/// A build for the exact `{os}_{arch}` is preferred, falling back to one keyed by `os` alone,
+/// such as a universal macOS app.
+pub fn select_asset<'a>(
+    files: &'a HashMap<String, String>,
+    os: &str,
+    arch: &str,
+) -> Option<(&'a str, &'a str)> {
+    [format!("{os}_{arch}"), os.to_string()]
+        .iter()
+        .find_map(|key| files.get_key_value(key))
+        .map(|(key, file)| (key.as_str(), file.as_str()))
+}
 
-    let arch = if cfg!(target_arch = "x86_64") {
-        "x86_64"
-    } else if cfg!(target_arch = "aarch64") {
-        "aarch64"
+/// Extension of a download that hasn't finished or been checked yet
+pub const PARTIAL_DOWNLOAD_EXTENSION: &str = "part";
+
+/// Name of the file a version is installed as inside `game_dir/versions/`.
+pub fn version_file_name(version: Version) -> String {
+    if cfg!(target_os = "windows") {
+        format!("{}.exe", version)
+    } else if cfg!(target_os = "macos") {
+        format!("{}.app", version)
     } else {
-        "unknown"
+        version.to_string()
+    }
+}
+
+/// Parses the version out of a file name in the versions directory, returning `None` for
+/// anything that isn't a version binary.
+///
+/// Only the name [`version_file_name`] gives a version is accepted, so other spellings like
+/// `0.3.0-release.0` for `0.3.0` never show up as a second install of the same version.
+pub fn version_from_file_name(file_name: &str) -> Option<Version> {
+    let stem = if cfg!(target_os = "windows") {
+        file_name.strip_suffix(".exe")?
+    } else if cfg!(target_os = "macos") {
+        file_name.strip_suffix(".app")?
+    } else {
+        file_name
     };
+    stem.parse::<Version>()
+        .ok()
+        .filter(|version| version_file_name(*version) == file_name)
+}
 
-    format!("{os}_{arch}")
+/// Whether `version` matches what was typed into a version filter box: a case-insensitive part
+/// of its name, with a leading `v` ignored so tags like `v0.3` can be pasted in.
+pub fn version_matches_filter(version: Version, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    let filter = filter.strip_prefix('v').unwrap_or(&filter);
+    version.to_string().contains(filter)
+}
+
+/// Guesses the version of a binary from a file name like `mineplace3d-0.3.0-alpha.1.exe`,
+/// trying everything after each `-` or `_` in the name.
+pub fn infer_version_from_file_name(file_name: &str) -> Option<Version> {
+    let stem = file_name
+        .strip_suffix(".exe")
+        .or_else(|| file_name.strip_suffix(".app"))
+        .unwrap_or(file_name);
+    std::iter::once(stem)
+        .chain(
+            stem.match_indices(['-', '_'])
+                .map(|(i, separator)| &stem[i + separator.len()..]),
+        )
+        .find_map(|candidate| candidate.parse().ok())
+}
+
+/// Schema written to `versions.json`. The legacy format, a bare array of version strings,
+/// counts as schema 1.
+pub const VERSIONS_SCHEMA: u32 = 2;
+
+/// What the launcher knows about an installed version.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledVersion {
+    pub version: Version,
+    /// Seconds since the Unix epoch at which the download finished
+    #[serde(default)]
+    pub installed_at: Option<u64>,
+    /// Size of the binary in bytes
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Verified SHA-256 digest of the binary
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Seconds since the Unix epoch at which the version was last launched
+    #[serde(default)]
+    pub last_played: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionsFile {
+    pub schema: u32,
+    pub versions: Vec<InstalledVersion>,
+}
+
+impl VersionsFile {
+    /// Parses `versions.json`, upgrading the legacy format. The returned flag is `true` if the
+    /// data was in the legacy format and should be written back.
+    ///
+    /// Files with a schema this launcher doesn't know, such as one written by a newer release,
+    /// are rejected rather than read with fields missing.
+    pub fn parse(data: &str) -> serde_json::Result<(Self, bool)> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum AnySchema {
+            Current(VersionsFile),
+            Legacy(Vec<Version>),
+        }
+
+        Ok(match serde_json::from_str(data)? {
+            AnySchema::Current(file) if file.schema == VERSIONS_SCHEMA => (file, false),
+            AnySchema::Current(file) => {
+                return Err(serde::de::Error::custom(format!(
+                    "unsupported schema {}, expected {}",
+                    file.schema, VERSIONS_SCHEMA
+                )));
+            }
+            AnySchema::Legacy(versions) => (
+                VersionsFile {
+                    schema: VERSIONS_SCHEMA,
+                    versions: versions
+                        .into_iter()
+                        .map(|version| InstalledVersion {
+                            version,
+                            ..InstalledVersion::default()
+                        })
+                        .collect(),
+                },
+                true,
+            ),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Manifest {
     pub latest: Latest,
     pub base: String,
+    #[serde(deserialize_with = "deserialize_versions")]
     pub versions: HashMap<Version, VersionEntry>,
 }
 
+/// Deserializes the manifest's version map, skipping any entry whose key does not parse as a
+/// [`Version`] so a single malformed tag doesn't make the whole manifest unusable.
+fn deserialize_versions<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<Version, VersionEntry>, D::Error> {
+    let raw = HashMap::<String, VersionEntry>::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .filter_map(|(tag, entry)| match tag.parse::<Version>() {
+            Ok(version) => Some((version, entry)),
+            Err(e) => {
+                tracing::warn!("Skipping manifest entry {:?}: {}", tag, e);
+                None
+            }
+        })
+        .collect())
+}
+
 impl Manifest {
-    pub fn get_chosen_version_entry<'a>(
-        &'a self,
+    pub fn get_chosen_version_entry(
+        &self,
         choice: VersionChoice,
-    ) -> Option<&'a VersionEntry> {
-        self.versions.get(&self.get_chosen_version(choice)?)
+        channels: &HashSet<VersionStage>,
+    ) -> Option<&VersionEntry> {
+        self.versions
+            .get(&self.get_chosen_version(choice, channels)?)
     }
 
-    pub fn get_chosen_version(&self, choice: VersionChoice) -> Option<Version> {
+    /// The version `choice` stands for, with "Latest" limited to the release `channels`.
+    pub fn get_chosen_version(
+        &self,
+        choice: VersionChoice,
+        channels: &HashSet<VersionStage>,
+    ) -> Option<Version> {
         match choice {
-            VersionChoice::Latest => self.get_latest_version(),
+            VersionChoice::Latest => self.get_latest_version_in(channels),
             VersionChoice::Specific(v) => Some(v),
         }
     }
 
+    /// The highest version in the manifest that `range` accepts.
+    ///
+    /// Exact versions are returned as they are, even if the manifest doesn't list them, so the
+    /// download reports the missing version itself.
+    pub fn resolve_range(&self, range: &VersionRange) -> Option<Version> {
+        match range {
+            VersionRange::Latest => self.get_latest_version(),
+            VersionRange::Exact(version) => Some(*version),
+            _ => range.best_match(self.versions.keys().copied()),
+        }
+    }
+
+    /// URL of the binary for `version` on this machine.
+    pub fn asset_url(&self, version: Version) -> Result<String, String> {
+        let entry = self
+            .versions
+            .get(&version)
+            .ok_or_else(|| format!("Version v{} not found in manifest", version))?;
+        let (_, file) = entry.asset().ok_or_else(|| {
+            let (os, arch) = (platform_os(), platform_arch());
+            let mut available: Vec<&str> = entry.files.keys().map(String::as_str).collect();
+            available.sort_unstable();
+            format!(
+                "Expected a binary for {}_{} or {} but v{} has: [{}]",
+                os,
+                arch,
+                os,
+                version,
+                available.join(", ")
+            )
+        })?;
+        Ok(format!(
+            "{}{}/{}",
+            self.base,
+            self.release_tag(version),
+            file
+        ))
+    }
+
+    /// The tag of the GitHub release `version` was published under.
+    pub fn release_tag(&self, version: Version) -> String {
+        let name = self
+            .versions
+            .get(&version)
+            .and_then(|entry| entry.real_name.clone())
+            .unwrap_or_else(|| version.to_string());
+        format!("v{}", name)
+    }
+
     pub fn get_latest_version(&self) -> Option<Version> {
         self.latest
             .stable
@@ -84,6 +836,15 @@ impl Manifest {
             .or(self.latest.alpha.as_ref())
             .copied()
     }
+
+    /// The latest stable, beta or alpha version, in that order of preference, whose release
+    /// channel is in `channels`.
+    pub fn get_latest_version_in(&self, channels: &HashSet<VersionStage>) -> Option<Version> {
+        [self.latest.stable, self.latest.beta, self.latest.alpha]
+            .into_iter()
+            .flatten()
+            .find(|version| channels.contains(&version.stage()))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -96,6 +857,485 @@ pub struct Latest {
 #[derive(Debug, Clone, Deserialize)]
 pub struct VersionEntry {
     pub files: HashMap<String, String>,
+    /// SHA-256 digests of the files, keyed by platform like `files`
+    #[serde(default)]
+    pub checksums: HashMap<String, String>,
     pub uploaded_on: String,
     pub real_name: Option<String>,
 }
+
+impl VersionEntry {
+    /// The file built for this machine, as its platform key and file name.
+    pub fn asset(&self) -> Option<(&str, &str)> {
+        select_asset(&self.files, &platform_os(), &platform_arch())
+    }
+
+    /// The SHA-256 digest of this machine's file in lowercase hex, if the manifest has one.
+    pub fn checksum(&self) -> Option<String> {
+        let (key, _) = self.asset()?;
+        self.checksums
+            .get(key)
+            .map(|checksum| checksum.trim_start_matches("sha256:").to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn iec_sizes_step_at_1024() {
+        assert_eq!(bytes_to_human_readable_u64(1023, ByteUnits::Iec), "1023 B");
+        assert_eq!(
+            bytes_to_human_readable_u64(1024, ByteUnits::Iec),
+            "1.00 KiB"
+        );
+        assert_eq!(
+            bytes_to_human_readable_u64(1536, ByteUnits::Iec),
+            "1.50 KiB"
+        );
+        assert_eq!(
+            bytes_to_human_readable_u64(3 * 1024 * 1024 * 1024, ByteUnits::Iec),
+            "3.00 GiB"
+        );
+    }
+
+    #[test]
+    fn si_sizes_step_at_1000() {
+        assert_eq!(bytes_to_human_readable_u64(999, ByteUnits::Si), "999 B");
+        assert_eq!(bytes_to_human_readable_u64(1000, ByteUnits::Si), "1.00 kB");
+        assert_eq!(bytes_to_human_readable_u64(1023, ByteUnits::Si), "1.02 kB");
+        assert_eq!(bytes_to_human_readable_u64(1536, ByteUnits::Si), "1.54 kB");
+    }
+
+    #[test]
+    fn huge_sizes_stop_at_the_largest_unit() {
+        assert_eq!(
+            bytes_to_human_readable_u64(5 * 1024u64.pow(5), ByteUnits::Iec),
+            "5120.00 TiB"
+        );
+        assert!(bytes_to_human_readable_u64(u64::MAX, ByteUnits::Si).ends_with(" TB"));
+    }
+
+    #[test]
+    fn float_sizes_keep_working() {
+        assert_eq!(bytes_to_human_readable(1023.0), "1023.00 B");
+        assert_eq!(bytes_to_human_readable(1024.0), "1.00 KB");
+        assert_eq!(bytes_to_human_readable(1536.0), "1.50 KB");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_dir_keeps_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = temp_dir("copy-dir");
+        let src = root.join("src");
+        let dst = root.join("dst");
+        std::fs::create_dir_all(src.join("worlds/main")).unwrap();
+        std::fs::write(src.join("worlds/main/level.dat"), "level").unwrap();
+        std::os::unix::fs::symlink("worlds/main", src.join("current")).unwrap();
+        let script = src.join("run.sh");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut last_progress = (0, 0);
+        copy_dir(&src, &dst, &mut |copied, total| {
+            last_progress = (copied, total)
+        })
+        .unwrap();
+
+        let link = dst.join("current");
+        assert!(
+            std::fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(std::fs::read_link(&link).unwrap(), Path::new("worlds/main"));
+        assert_eq!(
+            std::fs::read_to_string(link.join("level.dat")).unwrap(),
+            "level"
+        );
+        let mode = std::fs::metadata(dst.join("run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert_eq!(last_progress.0, last_progress.1);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn relative_time_in_minutes_hours_and_days() {
+        let ago = |secs| std::time::SystemTime::now() - std::time::Duration::from_secs(secs);
+        assert_eq!(relative_time(ago(10)), "just now");
+        assert_eq!(relative_time(ago(90)), "1 minute ago");
+        assert_eq!(relative_time(ago(5 * 60 + 10)), "5 minutes ago");
+        assert_eq!(relative_time(ago(3600 + 60)), "1 hour ago");
+        assert_eq!(relative_time(ago(23 * 3600 + 60)), "23 hours ago");
+        assert_eq!(relative_time(ago(86400 + 60)), "1 day ago");
+        assert_eq!(relative_time(ago(2 * 86400 + 60)), "2 days ago");
+    }
+
+    #[test]
+    fn relative_time_in_the_future_is_just_now() {
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(3600);
+        assert_eq!(relative_time(later), "just now");
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn game_command_without_wrapper_runs_the_binary() {
+        let exec_path = Path::new("/games/versions/0.3.0");
+        for wrapper in [None, Some(""), Some("   ")] {
+            let command = game_command(exec_path, wrapper);
+            assert_eq!(command.get_program(), exec_path.as_os_str());
+            assert_eq!(command.get_args().count(), 0);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn game_command_runs_the_binary_through_the_wrapper() {
+        let exec_path = Path::new("/games/versions/0.3.0");
+        let command = game_command(exec_path, Some("mangohud --dlsym \"gamemode run\""));
+        assert_eq!(command.get_program(), "mangohud");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["--dlsym", "gamemode run", "/games/versions/0.3.0"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn shared_library_names_match_only_that_library() {
+        for name in [
+            "libSDL2.so",
+            "libSDL2-2.0.so",
+            "libSDL2-2.0.so.0",
+            "libSDL2-2.0.so.0.3200.10",
+        ] {
+            assert!(is_shared_library(name, "libSDL2"), "{}", name);
+        }
+        for name in [
+            "libSDL2_ttf-2.0.so.0",
+            "libSDL2main.a",
+            "libSDL2.a",
+            "libSDL3.so.0",
+            "libSDL2-2.0.dylib",
+        ] {
+            assert!(!is_shared_library(name, "libSDL2"), "{}", name);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn find_library_scans_directories_in_order() {
+        let root = temp_dir("find-library");
+        let (empty, first, second) = (root.join("empty"), root.join("first"), root.join("second"));
+        for dir in [&empty, &first, &second] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(first.join("libSDL2_ttf-2.0.so.0"), "").unwrap();
+        std::fs::write(second.join("libSDL2-2.0.so.0"), "").unwrap();
+        let dirs = [
+            root.join("missing"),
+            empty.clone(),
+            first.clone(),
+            second.clone(),
+        ];
+        assert_eq!(
+            find_library(&dirs, "libSDL2"),
+            Some(second.join("libSDL2-2.0.so.0"))
+        );
+
+        std::fs::write(first.join("libSDL2.so"), "").unwrap();
+        assert_eq!(
+            find_library(&dirs, "libSDL2"),
+            Some(first.join("libSDL2.so"))
+        );
+        assert_eq!(find_library(&dirs[..2], "libSDL2"), None);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn dir_size_sums_nested_files() {
+        let root = temp_dir("dir-size");
+        std::fs::create_dir_all(root.join("worlds/main/region")).unwrap();
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+        std::fs::write(root.join("options.txt"), vec![0; 100]).unwrap();
+        std::fs::write(root.join("worlds/main/level.dat"), vec![0; 20]).unwrap();
+        std::fs::write(root.join("worlds/main/region/r.0.0"), vec![0; 4096]).unwrap();
+
+        assert_eq!(dir_size(&root).unwrap(), 100 + 20 + 4096);
+        assert_eq!(dir_size(&root.join("empty")).unwrap(), 0);
+        assert!(dir_size(&root.join("missing")).is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dir_size_does_not_follow_symlinks() {
+        let root = temp_dir("dir-size-symlink");
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        std::fs::write(root.join("data/blob"), vec![0; 1000]).unwrap();
+        std::os::unix::fs::symlink(root.join("data"), root.join("link")).unwrap();
+
+        assert_eq!(dir_size(&root).unwrap(), 1000);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn versions_file_upgrades_the_legacy_format() {
+        let (file, legacy) = VersionsFile::parse(r#"["0.3.0", "0.2.2-beta.1"]"#).unwrap();
+        assert!(legacy);
+        assert_eq!(file.schema, VERSIONS_SCHEMA);
+        let versions: Vec<String> = file
+            .versions
+            .iter()
+            .map(|r| r.version.to_string())
+            .collect();
+        assert_eq!(versions, ["0.3.0", "0.2.2-beta.1"]);
+        assert!(
+            file.versions
+                .iter()
+                .all(|r| r.installed_at.is_none() && r.sha256.is_none())
+        );
+    }
+
+    #[test]
+    fn versions_file_reads_the_current_format() {
+        let (file, legacy) = VersionsFile::parse(
+            r#"{"schema": 2, "versions": [
+                {"version": "0.3.0", "installed_at": 1700000000, "size": 1024, "sha256": "ab"},
+                {"version": "0.2.2"}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(!legacy);
+        assert_eq!(file.versions.len(), 2);
+        assert_eq!(file.versions[0].installed_at, Some(1700000000));
+        assert_eq!(file.versions[0].size, Some(1024));
+        assert_eq!(file.versions[0].sha256.as_deref(), Some("ab"));
+        assert_eq!(file.versions[1].installed_at, None);
+
+        let written = serde_json::to_string(&file).unwrap();
+        let (reread, legacy) = VersionsFile::parse(&written).unwrap();
+        assert!(!legacy);
+        assert_eq!(reread.versions, file.versions);
+    }
+
+    #[test]
+    fn versions_file_rejects_unknown_schemas() {
+        for schema in [0, 1, 3, 100] {
+            let data = format!(r#"{{"schema": {}, "versions": []}}"#, schema);
+            assert!(VersionsFile::parse(&data).is_err(), "schema {}", schema);
+        }
+        assert!(VersionsFile::parse(r#"{"versions": []}"#).is_err());
+    }
+
+    #[test]
+    fn write_atomic_replaces_the_target() {
+        let root = temp_dir("write-atomic");
+        let target = root.join("versions.json");
+        std::fs::write(&target, "old").unwrap();
+        // A temp file left behind by a crash is simply overwritten
+        std::fs::write(root.join(".versions.json.tmp"), "partial").unwrap();
+
+        write_atomic(&target, "new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert!(!root.join(".versions.json.tmp").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn write_atomic_keeps_the_target_when_writing_fails() {
+        let root = temp_dir("write-atomic-fail");
+        let target = root.join("versions.json");
+        std::fs::write(&target, "good").unwrap();
+        // The temp file can't be created where a directory is in the way
+        std::fs::create_dir_all(root.join(".versions.json.tmp/blocker")).unwrap();
+
+        assert!(write_atomic(&target, "partial").is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "good");
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn write_atomic_cleans_up_when_the_rename_fails() {
+        let root = temp_dir("write-atomic-rename");
+        // A non-empty directory can't be replaced by a file
+        let target = root.join("settings.json");
+        std::fs::create_dir_all(target.join("inner")).unwrap();
+
+        assert!(write_atomic(&target, "data").is_err());
+        assert!(target.join("inner").is_dir());
+        assert!(!root.join(".settings.json.tmp").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    /// The `files` of a manifest entry as published for a release.
+    const RELEASE_FILES: &str = r#"{
+        "linux_x86_64": "mineplace3d-linux-x86_64",
+        "linux_aarch64": "mineplace3d-linux-aarch64",
+        "windows_x86_64": "mineplace3d-windows-x86_64.exe",
+        "windows_x86": "mineplace3d-windows-x86.exe",
+        "macos": "mineplace3d-macos.app.zip"
+    }"#;
+
+    #[test]
+    fn select_asset_matches_each_platform_and_arch() {
+        let files: HashMap<String, String> = serde_json::from_str(RELEASE_FILES).unwrap();
+        for (os, arch, expected) in [
+            ("linux", "x86_64", "mineplace3d-linux-x86_64"),
+            ("linux", "aarch64", "mineplace3d-linux-aarch64"),
+            ("windows", "x86_64", "mineplace3d-windows-x86_64.exe"),
+            ("windows", "x86", "mineplace3d-windows-x86.exe"),
+        ] {
+            let key = format!("{}_{}", os, arch);
+            assert_eq!(
+                select_asset(&files, os, arch),
+                Some((key.as_str(), expected)),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn select_asset_falls_back_to_an_os_only_build() {
+        let files: HashMap<String, String> = serde_json::from_str(RELEASE_FILES).unwrap();
+        for arch in ["x86_64", "aarch64"] {
+            assert_eq!(
+                select_asset(&files, "macos", arch),
+                Some(("macos", "mineplace3d-macos.app.zip"))
+            );
+        }
+    }
+
+    #[test]
+    fn select_asset_finds_nothing_for_other_platforms() {
+        let files: HashMap<String, String> = serde_json::from_str(RELEASE_FILES).unwrap();
+        assert_eq!(select_asset(&files, "linux", "riscv64"), None);
+        assert_eq!(select_asset(&files, "windows", "aarch64"), None);
+        assert_eq!(select_asset(&files, "freebsd", "x86_64"), None);
+        assert_eq!(select_asset(&HashMap::new(), "linux", "x86_64"), None);
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn split_args_keeps_quoted_sections_together() {
+        assert_eq!(
+            split_args(r#"--fullscreen --world "My World" 'it''s' x"y z"w"#),
+            strings(&["--fullscreen", "--world", "My World", "its", "xy zw"])
+        );
+        assert_eq!(split_args("  "), Vec::<String>::new());
+        assert_eq!(split_args(r#""""#), strings(&[""]));
+    }
+
+    #[test]
+    fn split_args_unescapes_only_inside_double_quotes() {
+        assert_eq!(
+            split_args(r#""c\"d" "a\\b" "C:\Games" C:\Games\ 'x\"'"#),
+            strings(&["c\"d", "a\\b", "C:\\Games", "C:\\Games\\", "x\\\""])
+        );
+    }
+
+    #[test]
+    fn join_args_round_trips_through_split_args() {
+        for args in [
+            strings(&["a b", "c\"d"]),
+            strings(&["--fullscreen", "--world=My World"]),
+            strings(&["", "it's", "'quoted'", "\"\""]),
+            strings(&["C:\\Games\\x", "C:\\my dir\\", "a\\\"b c"]),
+            strings(&["tab\there", "new\nline"]),
+            Vec::new(),
+        ] {
+            assert_eq!(split_args(&join_args(&args)), args, "{}", join_args(&args));
+        }
+    }
+
+    #[test]
+    fn join_args_leaves_plain_args_alone() {
+        assert_eq!(
+            join_args(&strings(&["--fullscreen", "C:\\Games", "a b"])),
+            r#"--fullscreen C:\Games "a b""#
+        );
+        assert_eq!(join_args(&strings(&["c\"d"])), r#""c\"d""#);
+    }
+
+    #[test]
+    fn version_file_names_must_be_canonical() {
+        for input in ["0.3.0", "0.3.0-alpha.2", "0.3.0-release.1"] {
+            let version: Version = input.parse().unwrap();
+            assert_eq!(
+                version_from_file_name(&version_file_name(version)),
+                Some(version)
+            );
+        }
+        for input in [
+            "0.3.0-release",
+            "0.3.0-release.0",
+            "v0.3.0",
+            "0.3.0-alpha2",
+            "0.3.0+1",
+        ] {
+            let version: Version = input.parse().unwrap();
+            let name = version_file_name(version).replacen(&version.to_string(), input, 1);
+            assert_eq!(version_from_file_name(&name), None, "{}", name);
+        }
+        assert_eq!(version_from_file_name("versions.json"), None);
+        assert_eq!(version_from_file_name("0.3.0.part"), None);
+    }
+
+    #[test]
+    fn version_filter_ignores_case_and_a_leading_v() {
+        let version: Version = "0.3.0-alpha.2".parse().unwrap();
+        for filter in [
+            "",
+            "  ",
+            "0.3",
+            "v0.3",
+            "V0.3.0",
+            "ALPHA",
+            " v0.3.0-alpha.2 ",
+            "v",
+        ] {
+            assert!(version_matches_filter(version, filter), "{:?}", filter);
+        }
+        for filter in ["0.4", "beta", "vv0.3", "0.3.0-alpha.3"] {
+            assert!(!version_matches_filter(version, filter), "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn durations_show_hours_and_minutes() {
+        let minutes = |m: u64| std::time::Duration::from_secs(m * 60);
+        assert_eq!(format_duration(std::time::Duration::from_secs(59)), "0m");
+        assert_eq!(format_duration(minutes(45)), "45m");
+        assert_eq!(format_duration(minutes(60)), "1h 0m");
+        assert_eq!(
+            format_duration(minutes(125) + std::time::Duration::from_secs(30)),
+            "2h 5m"
+        );
+    }
+
+    #[test]
+    fn etas_show_seconds() {
+        assert_eq!(format_eta(-3.0), "0:00");
+        assert_eq!(format_eta(9.6), "0:10");
+        assert_eq!(format_eta(125.0), "2:05");
+        assert_eq!(format_eta(3725.0), "1:02:05");
+    }
+}
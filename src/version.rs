@@ -12,15 +12,40 @@ impl PartialOrd for VersionStage {
     }
 }
 
+impl VersionStage {
+    pub const ALL: [VersionStage; 3] = [
+        VersionStage::Alpha,
+        VersionStage::Beta,
+        VersionStage::Release,
+    ];
+
+    /// Whether versions in this stage are alpha or beta builds rather than full releases.
+    pub fn is_prerelease(self) -> bool {
+        !matches!(self, VersionStage::Release)
+    }
+
+    /// Position of the stage in release order, used to compare stages.
+    fn rank(self) -> u8 {
+        match self {
+            VersionStage::Alpha => 0,
+            VersionStage::Beta => 1,
+            VersionStage::Release => 2,
+        }
+    }
+}
+
 impl Ord for VersionStage {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        use VersionStage::*;
-        match (self, other) {
-            (Alpha, Alpha) | (Beta, Beta) | (Release, Release) => std::cmp::Ordering::Equal,
-            (Alpha, _) => std::cmp::Ordering::Less,
-            (Beta, Alpha) => std::cmp::Ordering::Greater,
-            (Beta, Release) => std::cmp::Ordering::Less,
-            (Release, _) => std::cmp::Ordering::Greater,
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl std::fmt::Display for VersionStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionStage::Alpha => write!(f, "alpha"),
+            VersionStage::Beta => write!(f, "beta"),
+            VersionStage::Release => write!(f, "release"),
         }
     }
 }
@@ -46,6 +71,49 @@ impl Default for Version {
     }
 }
 
+impl Version {
+    pub fn stage(&self) -> VersionStage {
+        self.stage
+    }
+
+    /// Whether this is an alpha or beta build.
+    pub fn is_prerelease(&self) -> bool {
+        self.stage.is_prerelease()
+    }
+
+    /// Compares in semver order: major, minor and patch first, then stage and build, so
+    /// `0.4.0-alpha.1` comes after `0.3.0`. [`Ord`] instead sorts every alpha before every
+    /// beta and release, which is what the version lists are grouped by.
+    pub fn cmp_semver(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch, self.stage, self.build).cmp(&(
+            other.major,
+            other.minor,
+            other.patch,
+            other.stage,
+            other.build,
+        ))
+    }
+}
+
+/// The newest of `remote` that is a later build of `installed`, sharing its
+/// major.minor.patch and stage, like `0.3.0-alpha.2` for `0.3.0-alpha.1`.
+pub fn newer_build_of(installed: &Version, remote: &[Version]) -> Option<Version> {
+    remote
+        .iter()
+        .filter(|v| {
+            (v.major, v.minor, v.patch, v.stage)
+                == (
+                    installed.major,
+                    installed.minor,
+                    installed.patch,
+                    installed.stage,
+                )
+        })
+        .filter(|v| *v > installed)
+        .max()
+        .copied()
+}
+
 impl PartialOrd for Version {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -68,39 +136,65 @@ impl std::str::FromStr for Version {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.trim().trim_start_matches('v');
+        // Build metadata (`+...`) carries no ordering information, so it is ignored
+        let s = s.split_once('+').map_or(s, |(version, _)| version);
         if s.is_empty() {
             return Err("Version string cannot be empty".to_string());
         }
+        if s.contains(char::is_whitespace) {
+            return Err(format!("Version \"{}\" cannot contain spaces", s));
+        }
 
         let parts: Vec<&str> = s.split('-').collect();
+        if parts.len() > 2 {
+            return Err("Version can only have a single stage suffix".to_string());
+        }
         let version_parts: Vec<&str> = parts[0].split('.').collect();
 
+        if version_parts.iter().any(|part| part.is_empty()) {
+            return Err(format!(
+                "Version \"{}\" has an empty number; check for extra dots",
+                parts[0]
+            ));
+        }
         if version_parts.len() != 3 {
             return Err("Version must be in the format major.minor.patch".to_string());
         }
 
-        let major = version_parts[0]
-            .parse::<u32>()
-            .map_err(|_| "Invalid major version".to_string())?;
-        let minor = version_parts[1]
-            .parse::<u32>()
-            .map_err(|_| "Invalid minor version".to_string())?;
-        let patch = version_parts[2]
-            .parse::<u32>()
-            .map_err(|_| "Invalid patch version".to_string())?;
+        let major = parse_number(version_parts[0], "major version")?;
+        let minor = parse_number(version_parts[1], "minor version")?;
+        let patch = parse_number(version_parts[2], "patch version")?;
 
         let (stage, build) = if parts.len() > 1 {
             let stage_parts: Vec<&str> = parts[1].split('.').collect();
-            let stage = match stage_parts[0] {
+            if stage_parts.iter().any(|part| part.is_empty()) {
+                return Err(format!(
+                    "Version stage \"{}\" has an empty part; check for extra dots or dashes",
+                    parts[1]
+                ));
+            }
+            if stage_parts.len() > 2 {
+                return Err("Version stage must be in the format stage.build".to_string());
+            }
+            // The build number may directly follow the stage name, as in `alpha2`
+            let (stage_name, inline_build) = stage_parts[0].split_at(
+                stage_parts[0]
+                    .find(|c: char| c.is_ascii_digit())
+                    .unwrap_or(stage_parts[0].len()),
+            );
+            let stage = match stage_name {
                 "alpha" => VersionStage::Alpha,
                 "beta" => VersionStage::Beta,
                 "release" => VersionStage::Release,
                 _ => return Err("Invalid version stage".to_string()),
             };
-            let build = if stage_parts.len() > 1 {
-                stage_parts[1]
-                    .parse::<u32>()
-                    .map_err(|_| "Invalid build number".to_string())?
+            let build = if !inline_build.is_empty() {
+                if stage_parts.len() > 1 {
+                    return Err("Version stage has more than one build number".to_string());
+                }
+                parse_number(inline_build, "build number")?
+            } else if stage_parts.len() > 1 {
+                parse_number(stage_parts[1], "build number")?
             } else {
                 0
             };
@@ -119,6 +213,16 @@ impl std::str::FromStr for Version {
     }
 }
 
+/// Parses one numeric component of a version, allowing only ASCII digits so that `+1` and
+/// similar spellings `u32::from_str` would take are rejected.
+fn parse_number(part: &str, name: &str) -> Result<u32, String> {
+    if !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid {} \"{}\"; expected a number", name, part));
+    }
+    part.parse::<u32>()
+        .map_err(|_| format!("The {} \"{}\" is too large", name, part))
+}
+
 impl std::fmt::Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
@@ -137,10 +241,25 @@ impl std::fmt::Display for Version {
     }
 }
 
+/// Reads a [`Version`] from its canonical string, borrowing it where the format allows so no
+/// `String` is allocated per entry.
+struct VersionVisitor;
+
+impl serde::de::Visitor<'_> for VersionVisitor {
+    type Value = Version;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "a version string like \"0.3.0\" or \"0.3.0-alpha.2\"")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Version, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for Version {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let s = String::deserialize(deserializer)?;
-        s.parse().map_err(serde::de::Error::custom)
+        deserializer.deserialize_str(VersionVisitor)
     }
 }
 
@@ -182,3 +301,479 @@ impl std::fmt::Display for VersionChoice {
         }
     }
 }
+
+/// A request for a version that may match several releases, resolved against the manifest by
+/// picking the highest matching version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionRange {
+    /// The version the manifest marks as latest
+    Latest,
+    Exact(Version),
+    /// `0.*` or `0.3.*`, any version with these leading numbers
+    Wildcard {
+        major: u32,
+        minor: Option<u32>,
+    },
+    /// Comma separated comparisons like `>=0.3.0,<0.4.0` that must all hold
+    Bounds(Vec<(Comparison, Version)>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn holds(self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::*;
+
+        match self {
+            Comparison::Less => ordering == Less,
+            Comparison::LessOrEqual => ordering != Greater,
+            Comparison::Greater => ordering == Greater,
+            Comparison::GreaterOrEqual => ordering != Less,
+            Comparison::Equal => ordering == Equal,
+        }
+    }
+}
+
+impl VersionRange {
+    /// Whether `version` satisfies the range. [`VersionRange::Latest`] matches nothing here, as
+    /// it depends on the manifest rather than the version.
+    pub fn matches(&self, version: Version) -> bool {
+        match self {
+            VersionRange::Latest => false,
+            VersionRange::Exact(exact) => version == *exact,
+            VersionRange::Wildcard { major, minor } => {
+                version.major == *major && minor.is_none_or(|minor| version.minor == minor)
+            }
+            VersionRange::Bounds(bounds) => bounds
+                .iter()
+                .all(|(comparison, bound)| comparison.holds(version.cmp_semver(bound))),
+        }
+    }
+
+    /// The highest of `versions` in the range, in semver order.
+    pub fn best_match(&self, versions: impl IntoIterator<Item = Version>) -> Option<Version> {
+        versions
+            .into_iter()
+            .filter(|v| self.matches(*v))
+            .max_by(Version::cmp_semver)
+    }
+}
+
+impl std::str::FromStr for VersionRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "latest" {
+            return Ok(VersionRange::Latest);
+        }
+
+        if let Some(prefix) = s.trim_start_matches('v').strip_suffix(".*") {
+            if prefix.is_empty() {
+                return Err(format!(
+                    "Wildcard \"{}\" needs a major version, like 0.* or 0.3.*",
+                    s
+                ));
+            }
+            let numbers = prefix
+                .split('.')
+                .map(|part| parse_number(part, "version number"))
+                .collect::<Result<Vec<u32>, String>>()?;
+            return match numbers[..] {
+                [major] => Ok(VersionRange::Wildcard { major, minor: None }),
+                [major, minor] => Ok(VersionRange::Wildcard {
+                    major,
+                    minor: Some(minor),
+                }),
+                _ => Err(format!(
+                    "Wildcard \"{}\" must be in the format major.* or major.minor.*",
+                    s
+                )),
+            };
+        }
+
+        if !s.starts_with(['<', '>', '=']) {
+            return s.parse().map(VersionRange::Exact);
+        }
+
+        let bounds = s
+            .split(',')
+            .map(|bound| {
+                let bound = bound.trim();
+                let (comparison, version) = [
+                    (">=", Comparison::GreaterOrEqual),
+                    ("<=", Comparison::LessOrEqual),
+                    (">", Comparison::Greater),
+                    ("<", Comparison::Less),
+                    ("=", Comparison::Equal),
+                ]
+                .into_iter()
+                .find_map(|(op, comparison)| {
+                    bound.strip_prefix(op).map(|version| (comparison, version))
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "Range part \"{}\" must start with one of >=, <=, >, < or =",
+                        bound
+                    )
+                })?;
+                Ok((comparison, version.parse::<Version>()?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(VersionRange::Bounds(bounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(major: u32, minor: u32, patch: u32, stage: VersionStage, build: u32) -> Version {
+        Version {
+            major,
+            minor,
+            patch,
+            stage,
+            build,
+        }
+    }
+
+    #[test]
+    fn display_and_parse_round_trip() {
+        for stage in VersionStage::ALL {
+            for build in [0, 1, 2, 10, u32::MAX] {
+                for (major, minor, patch) in [(0, 0, 0), (0, 3, 0), (1, 12, 7)] {
+                    let v = version(major, minor, patch, stage, build);
+                    assert_eq!(v.to_string().parse::<Version>().unwrap(), v, "{}", v);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn release_with_build_round_trips() {
+        let v = version(0, 3, 0, VersionStage::Release, 2);
+        assert_eq!(v.to_string(), "0.3.0-release.2");
+        assert_eq!("0.3.0-release.2".parse::<Version>().unwrap(), v);
+    }
+
+    #[test]
+    fn release_without_build_has_one_spelling() {
+        let plain = "0.3.0".parse::<Version>().unwrap();
+        assert_eq!("0.3.0-release".parse::<Version>().unwrap(), plain);
+        assert_eq!("0.3.0-release.0".parse::<Version>().unwrap(), plain);
+        assert_eq!(plain.to_string(), "0.3.0");
+    }
+
+    #[test]
+    fn parses_build_directly_after_stage() {
+        assert_eq!(
+            "0.3.0-alpha2".parse::<Version>().unwrap(),
+            version(0, 3, 0, VersionStage::Alpha, 2)
+        );
+    }
+
+    #[test]
+    fn ignores_build_metadata() {
+        assert_eq!(
+            "0.3.0-beta.5+meta".parse::<Version>().unwrap(),
+            version(0, 3, 0, VersionStage::Beta, 5)
+        );
+        assert_eq!(
+            "0.3.0+20240101".parse::<Version>().unwrap(),
+            version(0, 3, 0, VersionStage::Release, 0)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        for invalid in [
+            "0.3.0-gamma",
+            "0.3.0-gamma2",
+            "0.3.0-alpha2.3",
+            "0.3",
+            "0.3.0.1",
+            "0..3",
+            "0.3.0-",
+            "+1.0.0",
+            "",
+        ] {
+            assert!(invalid.parse::<Version>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn rejects_empty_numbers_with_a_clear_message() {
+        for malformed in ["0.3.0.", "0..3.0", ".3.0", "0.3.0-alpha.", "0.3.0-alpha..1"] {
+            let error = malformed.parse::<Version>().unwrap_err();
+            assert!(error.contains("empty"), "{}: {}", malformed, error);
+        }
+    }
+
+    #[test]
+    fn rejects_inner_whitespace() {
+        for malformed in ["  v 0.3.0", "0.3 .0", "0.3.0 -alpha"] {
+            let error = malformed.parse::<Version>().unwrap_err();
+            assert!(error.contains("spaces"), "{}: {}", malformed, error);
+        }
+    }
+
+    #[test]
+    fn accepts_surrounding_whitespace_and_a_leading_v() {
+        assert_eq!(
+            "  v0.3.0-beta.1 \n".parse::<Version>().unwrap(),
+            version(0, 3, 0, VersionStage::Beta, 1)
+        );
+    }
+
+    #[test]
+    fn rejects_signs_and_overflowing_numbers() {
+        assert!("0.+3.0".parse::<Version>().is_err());
+        assert!("0.3.-0".parse::<Version>().is_err());
+        let error = "0.3.99999999999".parse::<Version>().unwrap_err();
+        assert!(error.contains("too large"), "{}", error);
+    }
+
+    #[test]
+    fn only_alpha_and_beta_are_prereleases() {
+        assert!(VersionStage::Alpha.is_prerelease());
+        assert!(VersionStage::Beta.is_prerelease());
+        assert!(!VersionStage::Release.is_prerelease());
+        for (input, prerelease) in [
+            ("0.3.0-alpha", true),
+            ("0.3.0-alpha.4", true),
+            ("0.3.0-beta.1", true),
+            ("0.3.0", false),
+            ("0.3.0-release.2", false),
+        ] {
+            assert_eq!(
+                input.parse::<Version>().unwrap().is_prerelease(),
+                prerelease,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn stage_order_is_antisymmetric() {
+        for a in VersionStage::ALL {
+            for b in VersionStage::ALL {
+                assert_eq!(a.cmp(&b), b.cmp(&a).reverse(), "{} vs {}", a, b);
+                assert_eq!(a.cmp(&b) == std::cmp::Ordering::Equal, a == b);
+            }
+        }
+    }
+
+    #[test]
+    fn stage_order_is_transitive() {
+        for a in VersionStage::ALL {
+            for b in VersionStage::ALL {
+                for c in VersionStage::ALL {
+                    if a <= b && b <= c {
+                        assert!(a <= c, "{} <= {} <= {}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stages_sort_alpha_beta_release() {
+        assert!(VersionStage::Alpha < VersionStage::Beta);
+        assert!(VersionStage::Beta < VersionStage::Release);
+        let mut stages = VersionStage::ALL;
+        stages.reverse();
+        stages.sort();
+        assert_eq!(stages, VersionStage::ALL);
+    }
+
+    fn versions(inputs: &[&str]) -> Vec<Version> {
+        inputs.iter().map(|input| input.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn newer_build_of_finds_the_next_build() {
+        let installed = "0.3.0-alpha.1".parse().unwrap();
+        let remote = versions(&["0.3.0-alpha.1", "0.3.0-alpha.2"]);
+        assert_eq!(
+            newer_build_of(&installed, &remote),
+            Some("0.3.0-alpha.2".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn newer_build_of_picks_the_newest_build() {
+        let installed = "0.3.0-alpha.1".parse().unwrap();
+        let remote = versions(&["0.3.0-alpha.3", "0.3.0-alpha.2", "0.3.0-alpha.5"]);
+        assert_eq!(
+            newer_build_of(&installed, &remote),
+            Some("0.3.0-alpha.5".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn newer_build_of_ignores_other_stages_and_patches() {
+        let installed = "0.3.0-alpha.1".parse().unwrap();
+        let remote = versions(&["0.3.0-beta.4", "0.3.0", "0.3.1-alpha.2", "0.4.0-alpha.2"]);
+        assert_eq!(newer_build_of(&installed, &remote), None);
+    }
+
+    #[test]
+    fn newer_build_of_is_none_without_a_newer_build() {
+        let installed = "0.3.0-alpha.2".parse().unwrap();
+        assert_eq!(
+            newer_build_of(&installed, &versions(&["0.3.0-alpha.1", "0.3.0-alpha.2"])),
+            None
+        );
+        assert_eq!(newer_build_of(&installed, &[]), None);
+    }
+
+    #[test]
+    fn serde_round_trips_every_stage_and_build() {
+        for stage in VersionStage::ALL {
+            for build in [0, 1, 2, 10, u32::MAX] {
+                let v = version(0, 3, 1, stage, build);
+                let json = serde_json::to_string(&v).unwrap();
+                assert_eq!(json, format!("\"{}\"", v));
+                assert_eq!(
+                    serde_json::from_str::<Version>(&json).unwrap(),
+                    v,
+                    "{}",
+                    json
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn serde_writes_release_builds_with_their_stage() {
+        let v = version(0, 3, 0, VersionStage::Release, 2);
+        assert_eq!(serde_json::to_string(&v).unwrap(), r#""0.3.0-release.2""#);
+        assert_eq!(
+            serde_json::from_str::<Version>(r#""0.3.0-release.2""#).unwrap(),
+            v
+        );
+    }
+
+    #[test]
+    fn serde_round_trips_versions_as_map_keys() {
+        let times = std::collections::HashMap::from([
+            (version(0, 3, 0, VersionStage::Release, 2), 5u64),
+            (version(0, 3, 0, VersionStage::Alpha, 0), 7),
+        ]);
+        let json = serde_json::to_string(&times).unwrap();
+        assert_eq!(
+            serde_json::from_str::<std::collections::HashMap<Version, u64>>(&json).unwrap(),
+            times
+        );
+    }
+
+    #[test]
+    fn serde_rejects_invalid_versions() {
+        assert!(serde_json::from_str::<Version>(r#""0.3.0-gamma""#).is_err());
+        assert!(serde_json::from_str::<Version>("030").is_err());
+        assert!(serde_json::from_str::<Version>("null").is_err());
+    }
+
+    #[test]
+    fn semver_order_puts_numbers_before_stage() {
+        let ordered = versions(&[
+            "0.2.2",
+            "0.3.0-alpha.1",
+            "0.3.0-alpha.2",
+            "0.3.0-beta.1",
+            "0.3.0",
+            "0.3.0-release.1",
+            "0.3.1-alpha.1",
+            "0.4.0-alpha.1",
+        ]);
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.cmp_semver(b), i.cmp(&j), "{} vs {}", a, b);
+            }
+        }
+    }
+
+    fn range(input: &str) -> VersionRange {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn bounds_compare_in_semver_order() {
+        let bounds = range(">=0.3.0,<0.4.0");
+        assert!(bounds.matches("0.3.0".parse().unwrap()));
+        assert!(bounds.matches("0.3.5-beta.1".parse().unwrap()));
+        assert!(!bounds.matches("0.4.0".parse().unwrap()));
+        assert!(!bounds.matches("0.4.1-alpha.1".parse().unwrap()));
+        assert!(!bounds.matches("0.2.9".parse().unwrap()));
+        // Pre-releases come before their release, so they fall under `<0.4.0`
+        assert!(bounds.matches("0.4.0-alpha.1".parse().unwrap()));
+        assert!(!range(">=0.3.0,<0.4.0-alpha").matches("0.4.0-alpha.1".parse().unwrap()));
+        assert!(!bounds.matches("0.3.0-alpha.1".parse().unwrap()));
+        assert!(range(">0.3.0").matches("0.4.0-alpha.1".parse().unwrap()));
+        assert!(range("<=0.3.0").matches("0.3.0-beta.2".parse().unwrap()));
+    }
+
+    #[test]
+    fn best_match_is_the_highest_in_semver_order() {
+        let available = versions(&["0.3.0", "0.3.1-beta.1", "0.4.0-alpha.1", "0.2.2"]);
+        assert_eq!(
+            range(">=0.3.0,<0.4.0-alpha").best_match(available.clone()),
+            Some("0.3.1-beta.1".parse().unwrap())
+        );
+        assert_eq!(
+            range("0.*").best_match(available.clone()),
+            Some("0.4.0-alpha.1".parse().unwrap())
+        );
+        assert_eq!(
+            range("0.3.*").best_match(available.clone()),
+            Some("0.3.1-beta.1".parse().unwrap())
+        );
+        assert_eq!(range("1.*").best_match(available), None);
+    }
+
+    #[test]
+    fn parses_each_kind_of_range() {
+        assert_eq!(range("latest"), VersionRange::Latest);
+        assert_eq!(
+            range("v0.3.0"),
+            VersionRange::Exact(version(0, 3, 0, VersionStage::Release, 0))
+        );
+        assert_eq!(
+            range("0.3.*"),
+            VersionRange::Wildcard {
+                major: 0,
+                minor: Some(3)
+            }
+        );
+        assert_eq!(
+            range(">=0.3.0, <0.4.0"),
+            VersionRange::Bounds(vec![
+                (
+                    Comparison::GreaterOrEqual,
+                    version(0, 3, 0, VersionStage::Release, 0)
+                ),
+                (Comparison::Less, version(0, 4, 0, VersionStage::Release, 0)),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        let error = ".*".parse::<VersionRange>().unwrap_err();
+        assert!(error.contains("needs a major version"), "{}", error);
+        for malformed in [
+            "v.*", "0.3.0.*", "0..*", "x.*", ">=0.3.0,", "~0.3.0", ">=0.3",
+        ] {
+            assert!(malformed.parse::<VersionRange>().is_err(), "{}", malformed);
+        }
+    }
+}